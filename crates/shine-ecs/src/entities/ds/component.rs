@@ -1,4 +1,4 @@
-use crate::entities::Edge;
+use crate::entities::{Edge, Entity};
 use shine_graph::smat;
 use shred::{Read, ResourceId, SystemData, World, Write};
 use std::ops::{Deref, DerefMut};
@@ -11,6 +11,10 @@ pub use shine_graph::smat::{CSMatrixMask, MatrixMask};
 pub trait Component: 'static + Sync + Send + Sized {
     type Mask: 'static + Sync + Send + Default + MatrixMask;
     type Store: 'static + Sync + Send + Default + StoreMut<Item = Self>;
+
+    /// Maintain a column index to access the edges pointing to an entity (read_to, update_to).
+    /// It doubles the cost of the mask maintenance, thus it is disabled by default.
+    const COLUMN_INDEX: bool = false;
 }
 
 /// Contains the data instances assigned to the edge
@@ -25,6 +29,18 @@ impl<T> ComponentStore<T>
 where
     T: 'static + Sync + Send + Component,
 {
+    /// Create an empty store, the column index is created if it is enabled for the component.
+    pub fn new() -> Self {
+        let store = smat::SMatrix::default();
+        Self {
+            store: if T::COLUMN_INDEX {
+                store.with_column_index(Default::default())
+            } else {
+                store
+            },
+        }
+    }
+
     pub fn add(&mut self, edge: Edge, comp: <<T as Component>::Store as Store>::Item) {
         self.store.add(edge.from.id(), edge.to.id(), comp);
     }
@@ -68,6 +84,24 @@ where
     pub fn write(&mut self) -> smat::WrapRowWrite<'_, <T as Component>::Mask, <T as Component>::Store> {
         self.store.write()
     }
+
+    /// Access the components of the edges starting at the given entity.
+    pub fn read_from(&self, from: Entity) -> smat::RowRead<'_, <T as Component>::Mask, <T as Component>::Store> {
+        self.store.read_row(from.id())
+    }
+
+    /// Access the components of the edges pointing to the given entity.
+    pub fn read_to(&self, to: Entity) -> smat::ColumnRead<'_, <T as Component>::Mask, <T as Component>::Store> {
+        self.store.read_column(to.id())
+    }
+
+    pub fn update_from(&mut self, from: Entity) -> smat::RowUpdate<'_, <T as Component>::Mask, <T as Component>::Store> {
+        self.store.update_row(from.id())
+    }
+
+    pub fn update_to(&mut self, to: Entity) -> smat::ColumnUpdate<'_, <T as Component>::Mask, <T as Component>::Store> {
+        self.store.update_column(to.id())
+    }
 }
 
 impl<T> Default for ComponentStore<T>
@@ -75,9 +109,7 @@ where
     T: 'static + Sync + Send + Component,
 {
     fn default() -> Self {
-        Self {
-            store: Default::default(),
-        }
+        Self::new()
     }
 }
 
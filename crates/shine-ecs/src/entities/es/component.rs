@@ -3,7 +3,7 @@ use shine_graph::svec;
 use shred::{Read, ResourceId, SystemData, World, Write};
 use std::ops::{Deref, DerefMut};
 
-pub use shine_graph::svec::{DenseStore, Entry, HashStore};
+pub use shine_graph::svec::{Changes, DenseStore, Entry, HashStore, PackedStore, TrackedStore};
 pub use shine_graph::svec::{Store, StoreMut};
 
 /// Trait to assign storage policy to an entity data
@@ -68,6 +68,21 @@ where
     }
 }
 
+impl<T, S> ComponentStore<T>
+where
+    T: 'static + Sync + Send + Component<Store = TrackedStore<S>>,
+    S: 'static + StoreMut<Item = T>,
+{
+    pub fn changes(&self) -> &Changes {
+        self.store.changes()
+    }
+
+    /// Return the changes since the last drain and restart the tracking.
+    pub fn drain_changes(&mut self) -> Changes {
+        self.store.drain_changes()
+    }
+}
+
 impl<T> Default for ComponentStore<T>
 where
     T: 'static + Sync + Send + Component,
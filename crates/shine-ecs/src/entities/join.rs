@@ -1,6 +1,11 @@
 use crate::entities::Entity;
 use shine_graph::join;
-use shine_graph::traits::{IndexExcl, IndexLowerBound};
+#[cfg(feature = "parallel")]
+use shine_graph::traits::IndexSplit;
+use shine_graph::traits::{IndexExcl, IndexLowerBound, IndexRead};
+use std::iter::FromIterator;
+
+pub use shine_graph::join::{maybe, not};
 
 /// Iterator like trait that performs the merge.
 pub struct Join<S>
@@ -40,6 +45,40 @@ where
     }
 }
 
+/// Join of read-only stores is a real iterator as items don't borrow from the join.
+impl<S> Iterator for Join<S>
+where
+    S: IndexRead,
+{
+    type Item = (Entity, <S as IndexExcl<usize>>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Join::next(self)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<S> Join<S>
+where
+    S: IndexSplit + Send,
+{
+    /// Call f for each remaining item on the rayon thread pool.
+    pub fn par_for_each<F>(self, f: F)
+    where
+        F: Fn(Entity, <S as IndexExcl<usize>>::Item) + Sync,
+    {
+        self.inner.par_for_each(|id, e| f(Entity::from_id(id), e));
+    }
+
+    /// Call f for each remaining item on the rayon thread pool using the given chunk size.
+    pub fn par_for_each_chunked<F>(self, chunk_size: usize, f: F)
+    where
+        F: Fn(Entity, <S as IndexExcl<usize>>::Item) + Sync,
+    {
+        self.inner.par_for_each_chunked(chunk_size, |id, e| f(Entity::from_id(id), e));
+    }
+}
+
 /// Trait to create Join
 pub trait IntoJoin {
     type Store: IndexLowerBound<usize>;
@@ -79,6 +118,74 @@ pub trait IntoJoinExt: IntoJoin {
     {
         self.into_join().until(f);
     }
+
+    /// Return the first item of the join.
+    fn join_first(self) -> Option<(Entity, <Self::Store as IndexExcl<usize>>::Item)>
+    where
+        Self: Sized,
+    {
+        join::IntoJoinExt::join_first(self.into_join().inner).map(|(id, e)| (Entity::from_id(id), e))
+    }
+
+    /// Return the first item of the join where the predicate holds.
+    fn join_find<F>(self, mut f: F) -> Option<(Entity, <Self::Store as IndexExcl<usize>>::Item)>
+    where
+        F: FnMut(Entity, &<Self::Store as IndexExcl<usize>>::Item) -> bool,
+        Self: Sized,
+    {
+        join::IntoJoinExt::join_find(self.into_join().inner, |id, e| f(Entity::from_id(id), e))
+            .map(|(id, e)| (Entity::from_id(id), e))
+    }
+
+    /// Return the number of items in the join.
+    fn join_count(self) -> usize
+    where
+        Self: Sized,
+    {
+        join::IntoJoinExt::join_count(self.into_join().inner)
+    }
+
+    /// Collect the result of f called for each item.
+    fn join_collect<B, C, F>(self, mut f: F) -> C
+    where
+        F: FnMut(Entity, <Self::Store as IndexExcl<usize>>::Item) -> B,
+        C: FromIterator<B>,
+        Self: Sized,
+    {
+        join::IntoJoinExt::join_collect(self.into_join().inner, |id, e| f(Entity::from_id(id), e))
+    }
+
+    /// Collect the result of f called for each item, the None results are filtered out.
+    fn join_filter_collect<B, C, F>(self, mut f: F) -> C
+    where
+        F: FnMut(Entity, <Self::Store as IndexExcl<usize>>::Item) -> Option<B>,
+        C: FromIterator<B>,
+        Self: Sized,
+    {
+        join::IntoJoinExt::join_filter_collect(self.into_join().inner, |id, e| f(Entity::from_id(id), e))
+    }
 }
 
 impl<T: ?Sized> IntoJoinExt for T where T: IntoJoin {}
+
+#[cfg(feature = "parallel")]
+pub trait IntoParJoinExt: IntoJoin
+where
+    Self::Store: IndexSplit + Send,
+{
+    fn par_join_all<F>(self, f: F)
+    where
+        F: Fn(Entity, <Self::Store as IndexExcl<usize>>::Item) + Sync,
+        Self: Sized,
+    {
+        self.into_join().par_for_each(f);
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: ?Sized> IntoParJoinExt for T
+where
+    T: IntoJoin,
+    T::Store: IndexSplit + Send,
+{
+}
@@ -2,7 +2,7 @@ use shine_stdext::namedstore::Store as InnerStore;
 use shred::{Read, ResourceId, SystemData, Write};
 use std::ops::{Deref, DerefMut};
 
-pub use shine_stdext::namedstore::{Data, Index, ReadGuard, WriteGuard};
+pub use shine_stdext::namedstore::{Data, Index, LoadState, Loader, ReadGuard, WriteGuard};
 
 /// A thing wrapper around [InnerStore](InnerStore) to make it more ergonomic to the world.
 pub struct Store<D: Data> {
@@ -23,6 +23,17 @@ impl<D: Data> Store<D> {
         }
     }
 
+    /// Creates a new store where the data is loaded asynchronously by the given loader
+    pub fn new_with_loader<L: Loader<D>>(loader: L, worker_count: usize) -> Store<D>
+    where
+        D: 'static + Send,
+        D::Key: 'static,
+    {
+        Store {
+            inner: InnerStore::new_with_loader(loader, worker_count),
+        }
+    }
+
     pub fn read(&self) -> ReadGuard<'_, D> {
         self.inner.try_read().unwrap()
     }
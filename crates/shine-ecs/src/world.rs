@@ -143,7 +143,7 @@ impl EntityWorld for World {
     }
 
     fn register_edge_component<C: ds::Component>(&mut self) {
-        self.world.insert::<ds::ComponentStore<C>>(Default::default());
+        self.world.insert(ds::ComponentStore::<C>::new());
     }
 
     fn edge_components<C: ds::Component>(&self) -> Fetch<'_, ds::ComponentStore<C>> {
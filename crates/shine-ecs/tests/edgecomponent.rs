@@ -35,6 +35,35 @@ impl ds::Component for Weight {
     type Store = ds::ArenaStore<Self>;
 }
 
+#[derive(Debug)]
+struct Link {
+    id: usize,
+}
+impl ds::Component for Link {
+    type Mask = ds::CSMatrixMask;
+    type Store = ds::DenseStore<Self>;
+    const COLUMN_INDEX: bool = true;
+}
+
+#[test]
+fn test_column_index() {
+    init_test(module_path!());
+
+    let mut links = ds::ComponentStore::<Link>::default();
+    assert!(links.store.has_column_index());
+
+    links.add(Edge::from_ids(1, 2), Link { id: 12 });
+    links.add(Edge::from_ids(3, 2), Link { id: 32 });
+    links.add(Edge::from_ids(3, 4), Link { id: 34 });
+
+    let mut sources = Vec::new();
+    links.read_to(Entity::from_id(2)).join_all(|from, link| {
+        trace!("{:?} -> 2: {:?}", from, link);
+        sources.push((from.id(), link.id));
+    });
+    assert_eq!(sources, vec![(1, 12), (3, 32)]);
+}
+
 #[test]
 #[ignore]
 fn test_component() {
@@ -7,12 +7,14 @@ use syn::{Ident, IntSuffix, LitInt, Token};
 enum Op {
     And,
     Or,
+    Xor,
 }
 
 fn bitop_impl(count: usize, op: &Op) -> TokenStream {
     let (type_name, fn_name) = match op {
         Op::And => ("And", "and"),
         Op::Or => ("Or", "or"),
+        Op::Xor => ("Xor", "xor"),
     };
 
     let type_ident = Ident::new(&format!("{}{}", type_name, count), Span::call_site());
@@ -29,12 +31,13 @@ fn bitop_impl(count: usize, op: &Op) -> TokenStream {
     let members = &members;
 
     let (empty, get_level_count, get_block) = {
-        let (mut empty, mut get_level_count, mut get_block) = {
+        let (mut empty, mut get_level_count, mut get_block, mut get_block_xor) = {
             let m = &members[0];
             (
                 quote! { self.#m.is_empty() },
                 quote! { self.#m.get_level_count() },
                 quote! { self.#m.get_block(level, block) },
+                quote! { self.#m.get_block(level, block) },
             )
         };
         let mut i = 1;
@@ -53,11 +56,30 @@ fn bitop_impl(count: usize, op: &Op) -> TokenStream {
                     get_level_count = quote! { cmp::max( self.#m.get_level_count(), #get_level_count ) };
                     get_block = quote! { #get_block | self.#m.get_block(level, block) };
                 }
+
+                Op::Xor => {
+                    empty = quote! { #empty && self.#m.is_empty() };
+                    get_level_count = quote! { cmp::max( self.#m.get_level_count(), #get_level_count ) };
+                    get_block = quote! { #get_block | self.#m.get_block(level, block) };
+                    get_block_xor = quote! { #get_block_xor ^ self.#m.get_block(level, block) };
+                }
             }
 
             i += 1;
         }
 
+        if let Op::Xor = op {
+            // The upper levels are the union of the sources as a non-zero
+            // block may have an empty xor result. Only the bottom level is exact.
+            get_block = quote! {
+                if level == 0 {
+                    #get_block_xor
+                } else {
+                    #get_block
+                }
+            };
+        }
+
         (empty, get_level_count, get_block)
     };
 
@@ -117,6 +139,7 @@ fn bitop_impl(count: usize, op: &Op) -> TokenStream {
 fn bitop_tuple_impl(count: usize) -> TokenStream {
     let and_type = Ident::new(&format!("And{}", count), Span::call_site());
     let or_type = Ident::new(&format!("Or{}", count), Span::call_site());
+    let xor_type = Ident::new(&format!("Xor{}", count), Span::call_site());
 
     let generics: Vec<_> = (0..count)
         .map(|id| Ident::new(&format!("S{}", id), Span::/*def*/call_site()))
@@ -143,6 +166,11 @@ fn bitop_tuple_impl(count: usize) -> TokenStream {
             fn or(self) -> Self::Or {
                 #or_type::new(#(self.#index),*)
             }
+
+            type Xor = #xor_type<B, #(#generics),*>;
+            fn xor(self) -> Self::Xor {
+                #xor_type::new(#(self.#index),*)
+            }
         }
     };
 
@@ -159,9 +187,11 @@ pub fn impl_bitops_macro(input: proc_macro::TokenStream) -> Result<TokenStream,
         let count = lit.value();
         let and_type = bitop_impl(count as usize, &Op::And);
         let or_type = bitop_impl(count as usize, &Op::Or);
+        let xor_type = bitop_impl(count as usize, &Op::Xor);
         let bitop = bitop_tuple_impl(count as usize);
         ops.push(and_type);
         ops.push(or_type);
+        ops.push(xor_type);
         ops.push(bitop);
     }
 
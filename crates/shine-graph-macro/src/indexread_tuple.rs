@@ -0,0 +1,38 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitInt, Token};
+
+fn impl_indexread_for_tuple(count: usize) -> TokenStream {
+    let generics: Vec<_> = (0..count)
+        .map(|id| Ident::new(&format!("A{}", id), Span::/*def*/call_site()))
+        .collect();
+    let generics = &generics;
+
+    let type_impl = quote! {
+        /// Implement IndexRead for tuple of IndexRead
+        impl<#(#generics),*> IndexRead for (#(#generics,)*)
+        where
+            #(#generics: IndexRead),*
+        {
+        }
+    };
+
+    type_impl
+}
+
+pub fn impl_indexread_for_indexread_tuple(input: proc_macro::TokenStream) -> Result<TokenStream, String> {
+    let parser = Punctuated::<LitInt, Token![,]>::parse_terminated;
+    let list = parser.parse(input).map_err(|err| format!("Could not parse: {}", err))?;
+
+    let mut gen = Vec::new();
+
+    for lit in list {
+        let count = lit.value();
+        let tuple_impl = impl_indexread_for_tuple(count as usize);
+        gen.push(tuple_impl);
+    }
+
+    Ok(quote! {#(#gen)*})
+}
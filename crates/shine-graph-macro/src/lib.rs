@@ -35,3 +35,19 @@ pub fn impl_intojoin_for_intojoin_tuple(input: TokenStream) -> TokenStream {
         .unwrap_or_else(|err| panic!("compile_error: {}", err))
         .into()
 }
+
+mod indexread_tuple;
+#[proc_macro]
+pub fn impl_indexread_for_indexread_tuple(input: TokenStream) -> TokenStream {
+    indexread_tuple::impl_indexread_for_indexread_tuple(input)
+        .unwrap_or_else(|err| panic!("compile_error: {}", err))
+        .into()
+}
+
+mod indexsplit_tuple;
+#[proc_macro]
+pub fn impl_indexsplit_for_indexsplit_tuple(input: TokenStream) -> TokenStream {
+    indexsplit_tuple::impl_indexsplit_for_indexsplit_tuple(input)
+        .unwrap_or_else(|err| panic!("compile_error: {}", err))
+        .into()
+}
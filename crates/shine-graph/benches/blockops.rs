@@ -0,0 +1,74 @@
+#![feature(test)]
+
+extern crate test;
+
+use rand::Rng;
+use shine_graph::bits::{blockops, BitSet};
+use test::{black_box, Bencher};
+
+const ENTITY_COUNT: usize = 1_000_000;
+
+fn random_blocks() -> Vec<u64> {
+    let mut rng = rand::thread_rng();
+    (0..ENTITY_COUNT / 64).map(|_| rng.gen()).collect()
+}
+
+fn random_bitset() -> BitSet<u64> {
+    let mut rng = rand::thread_rng();
+    let mut set = BitSet::new_with_capacity(ENTITY_COUNT);
+    for _ in 0..ENTITY_COUNT / 2 {
+        set.add(rng.gen_range(0, ENTITY_COUNT));
+    }
+    set
+}
+
+macro_rules! bench_binary_op {
+    ($name:ident, $op:path) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut dst = random_blocks();
+            let src = random_blocks();
+            b.iter(|| {
+                $op(&mut dst, &src);
+                black_box(&dst);
+            });
+        }
+    };
+}
+
+bench_binary_op!(and_scalar, blockops::scalar::and_assign);
+bench_binary_op!(and_simd, blockops::and_assign);
+bench_binary_op!(or_scalar, blockops::scalar::or_assign);
+bench_binary_op!(or_simd, blockops::or_assign);
+bench_binary_op!(andnot_scalar, blockops::scalar::andnot_assign);
+bench_binary_op!(andnot_simd, blockops::andnot_assign);
+
+#[bench]
+fn popcount_scalar(b: &mut Bencher) {
+    let blocks = random_blocks();
+    b.iter(|| blockops::scalar::popcount(black_box(&blocks)));
+}
+
+#[bench]
+fn popcount_simd(b: &mut Bencher) {
+    let blocks = random_blocks();
+    b.iter(|| blockops::popcount(black_box(&blocks)));
+}
+
+#[bench]
+fn bitset_intersect(b: &mut Bencher) {
+    let set1 = random_bitset();
+    let set2 = random_bitset();
+    b.iter(|| {
+        let mut set = BitSet::new();
+        set.union_with(&set1);
+        set.intersect_with(&set2);
+        black_box(set.number_of_set())
+    });
+}
+
+#[bench]
+fn bitset_count_range(b: &mut Bencher) {
+    let set = random_bitset();
+    b.iter(|| set.count_range(black_box(17..ENTITY_COUNT - 17)));
+}
@@ -0,0 +1,56 @@
+use crate::algo::{nodes, successors};
+use crate::bits::BitSetViewExt;
+use crate::smat::{MatrixMask, SMatrix, Store};
+use std::collections::HashMap;
+
+fn find_root(parents: &mut HashMap<usize, usize>, node: usize) -> usize {
+    let mut root = node;
+    while let Some(&parent) = parents.get(&root) {
+        if parent == root {
+            break;
+        }
+        root = parent;
+    }
+
+    // path compression
+    let mut node = node;
+    while node != root {
+        let parent = parents.insert(node, root).unwrap();
+        node = parent;
+    }
+    root
+}
+
+/// Find the connected components of the graph ignoring the direction of the edges.
+/// The nodes of a component are sorted and the components are ordered by their smallest node.
+pub fn connected_components<M, S>(graph: &SMatrix<M, S>) -> Vec<Vec<usize>>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    let nodes = nodes(graph);
+    let mut parents: HashMap<usize, usize> = nodes.iter().map(|&n| (n, n)).collect();
+
+    for r in graph.row_mask.iter() {
+        for c in successors(graph, r) {
+            let a = find_root(&mut parents, r);
+            let b = find_root(&mut parents, c);
+            if a != b {
+                // keep the smaller node as root
+                parents.insert(a.max(b), a.min(b));
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut component_of_root = HashMap::new();
+    for node in nodes {
+        let root = find_root(&mut parents, node);
+        let id = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[id].push(node);
+    }
+    components
+}
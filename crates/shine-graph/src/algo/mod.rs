@@ -0,0 +1,55 @@
+use crate::bits::BitSetViewExt;
+use crate::smat::{DataRange, MatrixMask, SMatrix, Store};
+
+mod components;
+mod scc;
+mod shortestpath;
+mod toposort;
+mod traversal;
+
+pub use self::components::*;
+pub use self::scc::*;
+pub use self::shortestpath::*;
+pub use self::toposort::*;
+pub use self::traversal::*;
+
+/// Return the target nodes of the edges starting at the given node, in increasing order.
+pub(crate) fn successors<'a, M, S>(graph: &'a SMatrix<M, S>, node: usize) -> impl 'a + Iterator<Item = usize>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    let DataRange(start, end) = graph.mask.get_data_range(node);
+    let range = if start < end { start..end } else { 0..0 };
+    range.map(move |pos| graph.mask.get_column_index(pos.into()))
+}
+
+/// Return the target nodes and the data of the edges starting at the given node, in increasing order.
+pub(crate) fn weighted_successors<'a, M, S>(
+    graph: &'a SMatrix<M, S>,
+    node: usize,
+) -> impl 'a + Iterator<Item = (usize, &'a S::Item)>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    let DataRange(start, end) = graph.mask.get_data_range(node);
+    let range = if start < end { start..end } else { 0..0 };
+    range.map(move |pos| (graph.mask.get_column_index(pos.into()), graph.store.get(pos)))
+}
+
+/// Return all the nodes having an incoming or outgoing edge, in increasing order.
+pub(crate) fn nodes<M, S>(graph: &SMatrix<M, S>) -> Vec<usize>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    let mut nodes = Vec::new();
+    for r in graph.row_mask.iter() {
+        nodes.push(r);
+        nodes.extend(successors(graph, r));
+    }
+    nodes.sort();
+    nodes.dedup();
+    nodes
+}
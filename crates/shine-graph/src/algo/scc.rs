@@ -0,0 +1,77 @@
+use crate::algo::{nodes, successors};
+use crate::bits::BitSetViewExt;
+use crate::smat::{MatrixMask, SMatrix, Store};
+use crate::svec::VectorMask;
+use std::collections::HashMap;
+
+/// Find the strongly connected components using Tarjan's algorithm.
+/// The components are returned in reverse topological order (a component has no edge to a later one),
+/// the nodes of a component are sorted.
+pub fn strongly_connected_components<M, S>(graph: &SMatrix<M, S>) -> Vec<Vec<usize>>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    // (index, lowlink) of the visited nodes
+    let mut links: HashMap<usize, (usize, usize)> = HashMap::new();
+    let mut on_stack = VectorMask::new();
+    let mut component_stack = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+
+    for root in nodes(graph) {
+        if links.contains_key(&root) {
+            continue;
+        }
+
+        counter += 1;
+        links.insert(root, (counter, counter));
+        component_stack.push(root);
+        on_stack.add(root);
+        let mut stack = vec![(root, successors(graph, root))];
+
+        while let Some(top) = stack.last_mut() {
+            let node = top.0;
+            match top.1.next() {
+                Some(next) => match links.get(&next) {
+                    None => {
+                        counter += 1;
+                        links.insert(next, (counter, counter));
+                        component_stack.push(next);
+                        on_stack.add(next);
+                        stack.push((next, successors(graph, next)));
+                    }
+                    Some(&(next_index, _)) => {
+                        if on_stack.get(next) {
+                            let link = links.get_mut(&node).unwrap();
+                            link.1 = link.1.min(next_index);
+                        }
+                    }
+                },
+                None => {
+                    stack.pop();
+                    let (index, lowlink) = links[&node];
+                    if let Some(&(parent, _)) = stack.last() {
+                        let link = links.get_mut(&parent).unwrap();
+                        link.1 = link.1.min(lowlink);
+                    }
+                    if index == lowlink {
+                        let mut component = Vec::new();
+                        loop {
+                            let n = component_stack.pop().unwrap();
+                            on_stack.remove(n);
+                            component.push(n);
+                            if n == node {
+                                break;
+                            }
+                        }
+                        component.sort();
+                        components.push(component);
+                    }
+                }
+            }
+        }
+    }
+
+    components
+}
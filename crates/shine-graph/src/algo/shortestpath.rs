@@ -0,0 +1,136 @@
+use crate::algo::weighted_successors;
+use crate::smat::{MatrixMask, SMatrix, Store};
+use num_traits::Zero;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Result of a single source shortest path search
+#[derive(Debug)]
+pub struct ShortestPaths<W> {
+    source: usize,
+    distances: HashMap<usize, W>,
+    predecessors: HashMap<usize, usize>,
+}
+
+impl<W> ShortestPaths<W> {
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Return the length of the shortest path to the node, or None if it is not reachable.
+    pub fn distance(&self, node: usize) -> Option<&W> {
+        self.distances.get(&node)
+    }
+
+    /// Return the distance of all the reachable nodes.
+    pub fn distances(&self) -> &HashMap<usize, W> {
+        &self.distances
+    }
+
+    /// Return the nodes of the shortest path from the source to the node (inclusive).
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        if !self.distances.contains_key(&node) {
+            return None;
+        }
+        let mut path = vec![node];
+        let mut node = node;
+        while let Some(&prev) = self.predecessors.get(&node) {
+            path.push(prev);
+            node = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+struct HeapEntry<W> {
+    distance: W,
+    node: usize,
+}
+
+impl<W: PartialOrd> PartialEq for HeapEntry<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for HeapEntry<W> {}
+
+impl<W: PartialOrd> PartialOrd for HeapEntry<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for HeapEntry<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed to make a min-heap
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// Find the shortest paths from the source using the stored data as edge weights.
+/// The weights must be non-negative.
+pub fn dijkstra<M, S>(graph: &SMatrix<M, S>, source: usize) -> ShortestPaths<S::Item>
+where
+    M: MatrixMask,
+    S: Store,
+    S::Item: Clone + PartialOrd + Zero,
+{
+    dijkstra_by(graph, source, |w| w.clone())
+}
+
+/// Find the shortest paths from the source where the edge weights are computed from the stored data.
+/// The weights must be non-negative.
+pub fn dijkstra_by<M, S, W, F>(graph: &SMatrix<M, S>, source: usize, mut weight: F) -> ShortestPaths<W>
+where
+    M: MatrixMask,
+    S: Store,
+    W: Clone + PartialOrd + Zero,
+    F: FnMut(&S::Item) -> W,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(source, W::zero());
+    heap.push(HeapEntry {
+        distance: W::zero(),
+        node: source,
+    });
+
+    while let Some(HeapEntry { distance, node }) = heap.pop() {
+        if distances.get(&node).map(|d| distance > *d).unwrap_or(false) {
+            // outdated entry
+            continue;
+        }
+
+        for (next, data) in weighted_successors(graph, node) {
+            let w = weight(data);
+            debug_assert!(w.partial_cmp(&W::zero()) != Some(Ordering::Less), "Negative edge weight");
+            let next_distance = distance.clone() + w;
+            let is_shorter = match distances.get(&next) {
+                Some(d) => next_distance < *d,
+                None => true,
+            };
+            if is_shorter {
+                distances.insert(next, next_distance.clone());
+                predecessors.insert(next, node);
+                heap.push(HeapEntry {
+                    distance: next_distance,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    ShortestPaths {
+        source,
+        distances,
+        predecessors,
+    }
+}
@@ -0,0 +1,51 @@
+use crate::algo::{nodes, successors};
+use crate::bits::BitSetViewExt;
+use crate::smat::{MatrixMask, SMatrix, Store};
+use crate::svec::VectorMask;
+
+/// Error of the topological sort: the nodes of a cycle in the order of the edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// Order the nodes such that each edge points from an earlier node to a later one.
+/// If the graph is not acyclic, one of the cycles is returned.
+pub fn toposort<M, S>(graph: &SMatrix<M, S>) -> Result<Vec<usize>, Cycle>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    let mut visited = VectorMask::new();
+    let mut on_stack = VectorMask::new();
+    let mut order = Vec::new();
+
+    for root in nodes(graph) {
+        if visited.add(root) {
+            continue;
+        }
+        on_stack.add(root);
+        let mut stack = vec![(root, successors(graph, root))];
+
+        while let Some(top) = stack.last_mut() {
+            match top.1.next() {
+                Some(next) => {
+                    if on_stack.get(next) {
+                        let start = stack.iter().position(|(n, _)| *n == next).unwrap();
+                        return Err(Cycle(stack[start..].iter().map(|(n, _)| *n).collect()));
+                    }
+                    if !visited.add(next) {
+                        on_stack.add(next);
+                        stack.push((next, successors(graph, next)));
+                    }
+                }
+                None => {
+                    let (node, _) = stack.pop().unwrap();
+                    on_stack.remove(node);
+                    order.push(node);
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
@@ -0,0 +1,120 @@
+use crate::algo::successors;
+use crate::bits::BitSetViewExt;
+use crate::smat::{MatrixMask, SMatrix, Store};
+use crate::svec::VectorMask;
+use std::collections::VecDeque;
+
+/// Breadth first traversal of the nodes reachable from a start node.
+pub struct Bfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    graph: &'a SMatrix<M, S>,
+    visited: VectorMask,
+    queue: VecDeque<(usize, usize)>,
+}
+
+impl<'a, M, S> Bfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    pub fn new(graph: &'a SMatrix<M, S>, start: usize) -> Bfs<'a, M, S> {
+        let mut visited = VectorMask::new();
+        visited.add(start);
+        let mut queue = VecDeque::new();
+        queue.push_back((start, 0));
+        Bfs { graph, visited, queue }
+    }
+
+    /// Return the next node and its distance (number of edges) from the start node.
+    pub fn next_with_depth(&mut self) -> Option<(usize, usize)> {
+        let (node, depth) = self.queue.pop_front()?;
+        for next in successors(self.graph, node) {
+            if !self.visited.add(next) {
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((node, depth))
+    }
+}
+
+impl<'a, M, S> Iterator for Bfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.next_with_depth().map(|(node, _)| node)
+    }
+}
+
+/// Depth first (pre-order) traversal of the nodes reachable from a start node.
+/// The successors are visited in increasing order.
+pub struct Dfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    graph: &'a SMatrix<M, S>,
+    visited: VectorMask,
+    stack: Vec<usize>,
+}
+
+impl<'a, M, S> Dfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    pub fn new(graph: &'a SMatrix<M, S>, start: usize) -> Dfs<'a, M, S> {
+        Dfs {
+            graph,
+            visited: VectorMask::new(),
+            stack: vec![start],
+        }
+    }
+}
+
+impl<'a, M, S> Iterator for Dfs<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(node) = self.stack.pop() {
+            if self.visited.add(node) {
+                continue;
+            }
+            let start = self.stack.len();
+            let visited = &self.visited;
+            self.stack.extend(successors(self.graph, node).filter(|n| !visited.get(*n)));
+            // visit the smallest successor first
+            self.stack[start..].reverse();
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// Create a breadth first traversal from the start node.
+pub fn bfs<M, S>(graph: &SMatrix<M, S>, start: usize) -> Bfs<'_, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    Bfs::new(graph, start)
+}
+
+/// Create a depth first traversal from the start node.
+pub fn dfs<M, S>(graph: &SMatrix<M, S>, start: usize) -> Dfs<'_, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    Dfs::new(graph, start)
+}
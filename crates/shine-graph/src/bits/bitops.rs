@@ -7,6 +7,9 @@ pub trait BitOp<B: BitBlock> {
 
     type Or: BitSetView;
     fn or(self) -> Self::Or;
+
+    type Xor: BitSetView;
+    fn xor(self) -> Self::Xor;
 }
 
 use shine_graph_macro::impl_bitops;
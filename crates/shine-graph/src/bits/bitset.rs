@@ -1,5 +1,7 @@
+use crate::bits::blockops;
 use crate::bits::{BitBlock, BitPos, BitSetView, BitSetViewExt, MAX_LEVEL};
 use arrayvec::ArrayVec;
+use std::ops::Range;
 use std::slice;
 
 /// Hierarchical bitset.
@@ -158,6 +160,321 @@ impl<B: BitBlock> BitSet<B> {
             level.clear();
         }
     }
+
+    /// Return the first set bit.
+    pub fn first(&self) -> Option<usize> {
+        self.lower_bound(0)
+    }
+
+    /// Return the last set bit.
+    pub fn last(&self) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // descend along the highest set bits
+        let mut level = self.get_level_count() - 1;
+        let mut block = 0;
+        loop {
+            let pos = (block << B::bit_shift()) | highest_bit_pos(self.get_level(level)[block]);
+            if level == 0 {
+                return Some(pos);
+            }
+            level -= 1;
+            block = pos;
+        }
+    }
+
+    /// Return the first set bit after the given position.
+    pub fn next_after(&self, pos: usize) -> Option<usize> {
+        pos.checked_add(1).and_then(|pos| self.lower_bound(pos))
+    }
+
+    /// Return the last set bit before the given position.
+    pub fn prev_before(&self, pos: usize) -> Option<usize> {
+        let end = pos.min(self.capacity);
+        if end == 0 || self.is_empty() {
+            return None;
+        }
+
+        // find the highest set bit not after last, move upward on empty blocks
+        let level_count = self.get_level_count();
+        let mut level = 0;
+        let mut last = end - 1;
+        let masked_block = loop {
+            let block = self.get_block(level, last >> B::bit_shift());
+            let masked_block = block & (B::max_value() >> (B::bit_mask() - (last & B::bit_mask())));
+            if !masked_block.is_zero() {
+                break masked_block;
+            }
+            level += 1;
+            if level >= level_count || (last >> B::bit_shift()) == 0 {
+                return None;
+            }
+            last = (last >> B::bit_shift()) - 1;
+        };
+
+        // move downward along the highest set bits
+        let mut pos = ((last >> B::bit_shift()) << B::bit_shift()) | highest_bit_pos(masked_block);
+        while level > 0 {
+            level -= 1;
+            pos = (pos << B::bit_shift()) | highest_bit_pos(self.get_level(level)[pos]);
+        }
+        Some(pos)
+    }
+
+    /// Return the number of set bits in the given range.
+    pub fn count_range(&self, range: Range<usize>) -> usize {
+        let end = range.end.min(self.capacity);
+        if range.start >= end {
+            return 0;
+        }
+        if self.is_dense() {
+            let level = self.get_level(0);
+            let first = range.start >> B::bit_shift();
+            let last = (end - 1) >> B::bit_shift();
+            let count_block =
+                |block: usize| (level[block] & range_mask::<B>(block << B::bit_shift(), range.start, end)).count_ones() as usize;
+            return if first == last {
+                count_block(first)
+            } else {
+                count_block(first) + blockops::popcount(&level[first + 1..last]) + count_block(last)
+            };
+        }
+
+        let mut count = 0;
+        let mut next = self.lower_bound_block(range.start >> B::bit_shift());
+        while let Some(block) = next {
+            let block_start = block << B::bit_shift();
+            if block_start >= end {
+                break;
+            }
+            let bits = self.get_level(0)[block] & range_mask::<B>(block_start, range.start, end);
+            count += bits.count_ones() as usize;
+            next = self.lower_bound_block(block + 1);
+        }
+        count
+    }
+
+    /// Set all the bits in the given range.
+    pub fn add_range(&mut self, range: Range<usize>) {
+        if range.start >= range.end {
+            return;
+        }
+        self.increase_capacity_to(range.end);
+        let first = range.start >> B::bit_shift();
+        let last = (range.end - 1) >> B::bit_shift();
+        for block in first..=last {
+            let mask = range_mask::<B>(block << B::bit_shift(), range.start, range.end);
+            self.update_block(block, |b| b | mask);
+        }
+    }
+
+    /// Clear all the bits in the given range.
+    pub fn remove_range(&mut self, range: Range<usize>) {
+        let end = range.end.min(self.capacity);
+        let mut next = self.lower_bound_block(range.start >> B::bit_shift());
+        while let Some(block) = next {
+            let block_start = block << B::bit_shift();
+            if block_start >= end {
+                break;
+            }
+            let mask = range_mask::<B>(block_start, range.start, end);
+            self.update_block(block, |b| b & !mask);
+            next = self.lower_bound_block(block + 1);
+        }
+    }
+
+    /// Set the bits that are set in the other set.
+    pub fn union_with(&mut self, other: &BitSet<B>) {
+        self.increase_capacity_to(other.capacity);
+        if other.is_dense() {
+            let blocks = 0..other.get_level(0).len();
+            let old_count = blockops::popcount(&self.get_level(0)[blocks.clone()]);
+            blockops::or_assign(&mut self.get_level_mut(0)[blocks.clone()], other.get_level(0));
+            self.rebuild_levels(blocks, old_count);
+            return;
+        }
+        let mut next = other.lower_bound_block(0);
+        while let Some(block) = next {
+            let bits = other.get_level(0)[block];
+            self.update_block(block, |b| b | bits);
+            next = other.lower_bound_block(block + 1);
+        }
+    }
+
+    /// Keep only the bits that are set in the other set.
+    pub fn intersect_with(&mut self, other: &BitSet<B>) {
+        if self.is_dense() {
+            let blocks = 0..self.get_level(0).len();
+            let old_count = self.set_count;
+            blockops::and_assign(self.get_level_mut(0), other.get_level(0));
+            self.rebuild_levels(blocks, old_count);
+            return;
+        }
+        let mut next = self.lower_bound_block(0);
+        while let Some(block) = next {
+            let bits = other.get_block(0, block);
+            self.update_block(block, |b| b & bits);
+            next = self.lower_bound_block(block + 1);
+        }
+    }
+
+    /// Clear the bits that are set in the other set.
+    pub fn difference_with(&mut self, other: &BitSet<B>) {
+        if other.is_dense() {
+            let blocks = 0..self.get_level(0).len().min(other.get_level(0).len());
+            let old_count = blockops::popcount(&self.get_level(0)[blocks.clone()]);
+            blockops::andnot_assign(&mut self.get_level_mut(0)[blocks.clone()], other.get_level(0));
+            self.rebuild_levels(blocks, old_count);
+            return;
+        }
+        let mut next = other.lower_bound_block(0);
+        while let Some(block) = next {
+            if block >= self.get_level(0).len() {
+                break;
+            }
+            let bits = other.get_level(0)[block];
+            self.update_block(block, |b| b & !bits);
+            next = other.lower_bound_block(block + 1);
+        }
+    }
+
+    /// Flip the bits that are set in the other set.
+    pub fn symmetric_difference_with(&mut self, other: &BitSet<B>) {
+        self.increase_capacity_to(other.capacity);
+        let mut next = other.lower_bound_block(0);
+        while let Some(block) = next {
+            let bits = other.get_level(0)[block];
+            self.update_block(block, |b| b ^ bits);
+            next = other.lower_bound_block(block + 1);
+        }
+    }
+
+    /// Reduce the capacity to the last set bit and release the memory of the trailing empty blocks.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = match self.last() {
+            Some(last) => (last + 1 + B::bit_mask()) & !B::bit_mask(),
+            None => 0,
+        };
+        if capacity.max(B::bit_count()) >= self.capacity {
+            for level in self.levels.iter_mut() {
+                level.shrink_to_fit();
+            }
+            return;
+        }
+
+        let mut shrunk = BitSet::new();
+        shrunk.increase_capacity_to(capacity);
+        let mut next = self.lower_bound_block(0);
+        while let Some(block) = next {
+            let bits = self.get_level(0)[block];
+            shrunk.update_block(block, |_| bits);
+            next = self.lower_bound_block(block + 1);
+        }
+        *self = shrunk;
+    }
+
+    /// Return if the set has at least one bit set per bottom level block on average.
+    /// For such sets processing the whole bottom level is faster than skipping the empty blocks using the upper levels.
+    fn is_dense(&self) -> bool {
+        self.set_count >= self.get_level(0).len()
+    }
+
+    /// Recompute the set count and the upper levels after a range of the bottom level was modified.
+    /// The number of bits set in the range before the modification is given by old_count.
+    fn rebuild_levels(&mut self, blocks: Range<usize>, old_count: usize) {
+        self.set_count = self.set_count - old_count + blockops::popcount(&self.get_level(0)[blocks.clone()]);
+        let (mut start, mut end) = (blocks.start, blocks.end);
+        for level in 1..self.get_level_count() {
+            start >>= B::bit_shift();
+            end = (end + B::bit_mask()) >> B::bit_shift();
+            for block in start..end {
+                let lower = self.get_level(level - 1);
+                let mut bits = B::zero();
+                for (offset, child) in lower.iter().skip(block << B::bit_shift()).take(B::bit_count()).enumerate() {
+                    if !child.is_zero() {
+                        bits = bits | (B::one() << offset);
+                    }
+                }
+                self.get_level_mut(level)[block] = bits;
+            }
+        }
+    }
+
+    /// Return the first non-zero block of the bottom level not before the given block.
+    fn lower_bound_block(&self, block: usize) -> Option<usize> {
+        if self.levels.is_empty() {
+            if block == 0 && !self.top.is_zero() {
+                Some(0)
+            } else {
+                None
+            }
+        } else {
+            LevelView { set: self, level: 1 }.lower_bound(block)
+        }
+    }
+
+    /// Modify a block of the bottom level and update the set count and the parent levels.
+    fn update_block<F: FnOnce(B) -> B>(&mut self, block: usize, f: F) {
+        let old = self.get_level(0)[block];
+        let new = f(old);
+        if old == new {
+            return;
+        }
+        self.get_level_mut(0)[block] = new;
+        self.set_count = self.set_count + new.count_ones() as usize - old.count_ones() as usize;
+
+        // update levels while a block changes between zero and non-zero
+        let mut changed = old.is_zero() != new.is_zero();
+        let mut idx = BitPos::from_pos(block << B::bit_shift(), self.get_level_count());
+        while changed && idx.level_up() {
+            let is_zero = self.get_level(idx.level() - 1)[idx.block() << B::bit_shift() | idx.offset()].is_zero();
+            changed = if is_zero {
+                self.unset_level(&idx)
+            } else {
+                self.set_level(&idx)
+            };
+        }
+    }
+}
+
+/// Return the position of the highest set bit of a non-zero block.
+fn highest_bit_pos<B: BitBlock>(block: B) -> usize {
+    B::bit_mask() - block.leading_zeros() as usize
+}
+
+/// Return the mask of the bits of the block starting at block_start that are in the start..end range.
+fn range_mask<B: BitBlock>(block_start: usize, start: usize, end: usize) -> B {
+    let lo = start.saturating_sub(block_start).min(B::bit_count());
+    let hi = end.saturating_sub(block_start).min(B::bit_count());
+    if lo >= hi {
+        B::zero()
+    } else {
+        (B::max_value() >> (B::bit_count() - (hi - lo))) << lo
+    }
+}
+
+/// View of the upper levels of a BitSet, where each bit indicates a non-zero block of the bottom level.
+struct LevelView<'a, B: BitBlock> {
+    set: &'a BitSet<B>,
+    level: usize,
+}
+
+impl<'a, B: BitBlock> BitSetView for LevelView<'a, B> {
+    type Bits = B;
+
+    fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    fn get_level_count(&self) -> usize {
+        self.set.get_level_count() - self.level
+    }
+
+    fn get_block(&self, level: usize, block: usize) -> B {
+        self.set.get_block(level + self.level, block)
+    }
 }
 
 impl<B: BitBlock> Default for BitSet<B> {
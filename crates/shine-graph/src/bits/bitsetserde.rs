@@ -0,0 +1,104 @@
+use crate::bits::{BitBlock, BitSet, BitSetViewExt};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+/// Largest capacity accepted from a serialized input, larger values are considered to be corrupted.
+const MAX_CAPACITY: usize = 1 << 32;
+
+/// Number of bits the capacity may exceed the (block aligned) end of the set bits by.
+/// The capacity of a set is not shrunk on remove, but a larger gap is considered to be corrupted.
+pub(crate) const CAPACITY_SLACK: usize = 1 << 16;
+
+/// Largest accepted ratio of the index range and the number of items, sparser inputs are considered to be corrupted
+/// as the stores and masks may allocate memory for the whole index range.
+const MAX_SPARSITY: usize = 1024;
+
+/// Return the largest index range accepted from a serialized input for the given number of items.
+pub(crate) fn max_index_range(count: usize) -> usize {
+    count.saturating_mul(MAX_SPARSITY).saturating_add(CAPACITY_SLACK)
+}
+
+/// Serialized form of a BitSet: the capacity and the run-length encoded set bits.
+/// The upper levels are not stored as they are derived from the set bits.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BitSetData {
+    capacity: usize,
+    // (first bit, length) of the continuous runs of set bits
+    runs: Vec<(usize, usize)>,
+}
+
+impl BitSetData {
+    /// Return the number of set bits, or None on overflow.
+    pub(crate) fn number_of_set(&self) -> Option<usize> {
+        self.runs.iter().try_fold(0usize, |count, &(_, len)| count.checked_add(len))
+    }
+
+    /// Return the end of the last run, or None on overflow.
+    pub(crate) fn end(&self) -> Option<usize> {
+        self.runs
+            .iter()
+            .try_fold(0usize, |end, &(start, len)| start.checked_add(len).map(|e| end.max(e)))
+    }
+
+    /// Validate the data against the corrupted inputs before any allocation and create the BitSet.
+    pub(crate) fn into_bitset<B: BitBlock, E: Error>(self) -> Result<BitSet<B>, E> {
+        if self.capacity > MAX_CAPACITY {
+            return Err(E::custom(format!(
+                "capacity {} exceeds the limit {}",
+                self.capacity, MAX_CAPACITY
+            )));
+        }
+        let end = self.end().ok_or_else(|| E::custom("run overflow"))?;
+        if end > self.capacity {
+            return Err(E::custom(format!(
+                "runs end at {} exceeding the capacity {}",
+                end, self.capacity
+            )));
+        }
+        let aligned_end = (end + B::bit_mask()) & !B::bit_mask();
+        if self.capacity > aligned_end + CAPACITY_SLACK {
+            return Err(E::custom(format!(
+                "capacity {} is too large for the runs ending at {}",
+                self.capacity, end
+            )));
+        }
+
+        let mut set = BitSet::new();
+        set.increase_capacity_to(self.capacity);
+        for (start, len) in self.runs {
+            set.add_range(start..start + len);
+        }
+        Ok(set)
+    }
+}
+
+impl<B: BitBlock> Serialize for BitSet<B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for bit in self.iter() {
+            match runs.last_mut() {
+                Some(run) if run.0 + run.1 == bit => run.1 += 1,
+                _ => runs.push((bit, 1)),
+            }
+        }
+
+        BitSetData {
+            capacity: self.capacity(),
+            runs,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, B: BitBlock> Deserialize<'de> for BitSet<B> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BitSetData::deserialize(deserializer)?.into_bitset()
+    }
+}
@@ -0,0 +1,159 @@
+//! Bitwise operations on contiguous slices of blocks.
+//! With the simd feature enabled the operations are vectorized on x86_64 when the cpu supports it (runtime detected),
+//! the scalar implementation is used everywhere else.
+//! In the binary operations src is treated as zero-extended to the length of dst.
+
+use crate::bits::BitBlock;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use std::{mem, slice};
+
+/// Scalar implementation of the block operations.
+pub mod scalar {
+    use crate::bits::BitBlock;
+
+    /// Compute dst &= src.
+    pub fn and_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+        let len = dst.len().min(src.len());
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = *d & *s;
+        }
+        for d in dst[len..].iter_mut() {
+            *d = B::zero();
+        }
+    }
+
+    /// Compute dst |= src.
+    pub fn or_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = *d | *s;
+        }
+    }
+
+    /// Compute dst &= !src.
+    pub fn andnot_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = *d & !*s;
+        }
+    }
+
+    /// Return the number of set bits.
+    pub fn popcount<B: BitBlock>(blocks: &[B]) -> usize {
+        blocks.iter().map(|b| b.count_ones() as usize).sum()
+    }
+}
+
+/// The blocks are plain integers without padding, thus they can be processed as bytes.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+fn as_bytes<B: BitBlock>(blocks: &[B]) -> &[u8] {
+    unsafe { slice::from_raw_parts(blocks.as_ptr() as *const u8, mem::size_of_val(blocks)) }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+fn as_bytes_mut<B: BitBlock>(blocks: &mut [B]) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(blocks.as_mut_ptr() as *mut u8, mem::size_of_val(blocks)) }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod x86 {
+    use std::arch::x86_64::*;
+    use std::ptr;
+
+    const LANE: usize = 32;
+
+    macro_rules! impl_avx2_op {
+        ($name:ident, $op:ident, $scalar:expr) => {
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn $name(dst: &mut [u8], src: &[u8]) {
+                let len = dst.len().min(src.len());
+                let simd_len = len - len % LANE;
+                let mut i = 0;
+                while i < simd_len {
+                    let d = _mm256_loadu_si256(dst.as_ptr().add(i) as *const __m256i);
+                    let s = _mm256_loadu_si256(src.as_ptr().add(i) as *const __m256i);
+                    _mm256_storeu_si256(dst.as_mut_ptr().add(i) as *mut __m256i, $op(d, s));
+                    i += LANE;
+                }
+                for i in simd_len..len {
+                    dst[i] = $scalar(dst[i], src[i]);
+                }
+            }
+        };
+    }
+
+    // note: _mm256_andnot_si256(a, b) computes !a & b
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn andnot_rev(d: __m256i, s: __m256i) -> __m256i {
+        _mm256_andnot_si256(s, d)
+    }
+
+    impl_avx2_op!(and_assign_avx2, _mm256_and_si256, |d: u8, s: u8| d & s);
+    impl_avx2_op!(or_assign_avx2, _mm256_or_si256, |d: u8, s: u8| d | s);
+    impl_avx2_op!(andnot_assign_avx2, andnot_rev, |d: u8, s: u8| d & !s);
+
+    #[target_feature(enable = "popcnt")]
+    pub unsafe fn popcount_popcnt(bytes: &[u8]) -> usize {
+        let words = bytes.len() / 8;
+        let mut count = 0;
+        for i in 0..words {
+            let word = ptr::read_unaligned(bytes.as_ptr().add(i * 8) as *const u64);
+            count += _popcnt64(word as i64) as usize;
+        }
+        for b in &bytes[words * 8..] {
+            count += b.count_ones() as usize;
+        }
+        count
+    }
+}
+
+/// Compute dst &= src.
+pub fn and_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            let len = dst.len().min(src.len());
+            let (head, tail) = dst.split_at_mut(len);
+            unsafe { x86::and_assign_avx2(as_bytes_mut(head), as_bytes(src)) };
+            for d in tail.iter_mut() {
+                *d = B::zero();
+            }
+            return;
+        }
+    }
+    scalar::and_assign(dst, src)
+}
+
+/// Compute dst |= src.
+pub fn or_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { x86::or_assign_avx2(as_bytes_mut(dst), as_bytes(src)) };
+            return;
+        }
+    }
+    scalar::or_assign(dst, src)
+}
+
+/// Compute dst &= !src.
+pub fn andnot_assign<B: BitBlock>(dst: &mut [B], src: &[B]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { x86::andnot_assign_avx2(as_bytes_mut(dst), as_bytes(src)) };
+            return;
+        }
+    }
+    scalar::andnot_assign(dst, src)
+}
+
+/// Return the number of set bits.
+pub fn popcount<B: BitBlock>(blocks: &[B]) -> usize {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("popcnt") {
+            return unsafe { x86::popcount_popcnt(as_bytes(blocks)) };
+        }
+    }
+    scalar::popcount(blocks)
+}
@@ -1,12 +1,18 @@
 mod bitblock;
 mod bititer;
 mod bitset;
+#[cfg(feature = "serialize")]
+mod bitsetserde;
 mod bitsetview;
 
 pub mod bitconst;
 pub mod bitops;
+pub mod blockops;
 
 pub use self::bitblock::*;
 pub use self::bititer::*;
 pub use self::bitset::*;
 pub use self::bitsetview::*;
+
+#[cfg(feature = "serialize")]
+pub(crate) use self::bitsetserde::{max_index_range, BitSetData, CAPACITY_SLACK};
@@ -1,4 +1,5 @@
-use crate::traits::{IndexExcl, IndexLowerBound};
+use crate::traits::{IndexExcl, IndexLowerBound, IndexRead, IndexSplit};
+use std::iter::{self, FromIterator};
 use std::ops::Range;
 
 /// Iterator like trait that performs the merge.
@@ -34,10 +35,16 @@ where
         }
     }
 
+    /// Call f for each remaining item.
+    /// The join must have a bounded source, unbounded sources (maybe, not, write) can be iterated only using until.
     pub fn for_each<F>(&mut self, mut f: F)
     where
         F: FnMut(usize, S::Item),
     {
+        assert!(
+            self.remaining_range.start >= self.remaining_range.end || self.remaining_range.end != usize::max_value(),
+            "Join has no bounded source, maybe and not require a bounding partner"
+        );
         while let Some((id, e)) = self.next() {
             f(id, e);
         }
@@ -55,6 +62,18 @@ where
     }
 }
 
+/// Join of read-only stores is a real iterator as items don't borrow from the join.
+impl<S> Iterator for Join<S>
+where
+    S: IndexRead,
+{
+    type Item = (usize, <S as IndexExcl<usize>>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Join::next(self)
+    }
+}
+
 /// Trait to create Join
 pub trait IntoJoin {
     type Store: IndexLowerBound<usize>;
@@ -62,6 +81,17 @@ pub trait IntoJoin {
     fn into_join(self) -> Join<<Self as IntoJoin>::Store>;
 }
 
+impl<S> IntoJoin for Join<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    type Store = S;
+
+    fn into_join(self) -> Join<S> {
+        self
+    }
+}
+
 pub trait IntoJoinExt: IntoJoin {
     fn join_all<F>(self, f: F)
     where
@@ -78,9 +108,202 @@ pub trait IntoJoinExt: IntoJoin {
     {
         self.into_join().until(f);
     }
+
+    /// Return the first item of the join.
+    fn join_first(self) -> Option<(usize, <Self::Store as IndexExcl<usize>>::Item)>
+    where
+        Self: Sized,
+    {
+        self.into_join().next()
+    }
+
+    /// Return the first item of the join where the predicate holds.
+    fn join_find<F>(self, mut f: F) -> Option<(usize, <Self::Store as IndexExcl<usize>>::Item)>
+    where
+        F: FnMut(usize, &<Self::Store as IndexExcl<usize>>::Item) -> bool,
+        Self: Sized,
+    {
+        let mut join = self.into_join();
+        while let Some((id, e)) = join.next() {
+            if f(id, &e) {
+                return Some((id, e));
+            }
+        }
+        None
+    }
+
+    /// Return the number of items in the join.
+    fn join_count(self) -> usize
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        self.into_join().for_each(|_, _| count += 1);
+        count
+    }
+
+    /// Collect the result of f called for each item.
+    fn join_collect<B, C, F>(self, mut f: F) -> C
+    where
+        F: FnMut(usize, <Self::Store as IndexExcl<usize>>::Item) -> B,
+        C: FromIterator<B>,
+        Self: Sized,
+    {
+        let mut join = self.into_join();
+        C::from_iter(iter::from_fn(|| join.next().map(|(id, e)| f(id, e))))
+    }
+
+    /// Collect the result of f called for each item, the None results are filtered out.
+    fn join_filter_collect<B, C, F>(self, mut f: F) -> C
+    where
+        F: FnMut(usize, <Self::Store as IndexExcl<usize>>::Item) -> Option<B>,
+        C: FromIterator<B>,
+        Self: Sized,
+    {
+        let mut join = self.into_join();
+        C::from_iter(iter::from_fn(|| loop {
+            match join.next() {
+                Some((id, e)) => {
+                    if let Some(b) = f(id, e) {
+                        return Some(b);
+                    }
+                }
+                None => return None,
+            }
+        }))
+    }
 }
 
 impl<T: ?Sized> IntoJoinExt for T where T: IntoJoin {}
 
 use shine_graph_macro::impl_intojoin_for_intojoin_tuple;
 impl_intojoin_for_intojoin_tuple! {2,3,4,5,6,7,8,9,10}
+
+/// Wrapper to join an optional source. It does not constrain the iteration and the
+/// Item is None for the indices missing from the source.
+pub struct Maybe<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    range: Range<usize>,
+    store: S,
+}
+
+/// Create a join source that yields Option<Item> without constraining the iteration.
+/// The source is unbounded, thus it has to be joined with at least one bounded source.
+pub fn maybe<J: IntoJoin>(source: J) -> Maybe<J::Store> {
+    let (range, store) = source.into_join().into_parts();
+    Maybe { range, store }
+}
+
+impl<S> IndexExcl<usize> for Maybe<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    type Item = Option<S::Item>;
+
+    fn index(&mut self, idx: usize) -> Self::Item {
+        if idx >= self.range.start && idx < self.range.end && self.store.lower_bound(idx) == Some(idx) {
+            Some(self.store.index(idx))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> IndexLowerBound<usize> for Maybe<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    fn lower_bound(&mut self, idx: usize) -> Option<usize> {
+        Some(idx)
+    }
+}
+
+impl<S> IndexRead for Maybe<S> where S: IndexRead {}
+
+unsafe impl<S> IndexSplit for Maybe<S>
+where
+    S: IndexSplit,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        Maybe {
+            range: self.range.clone(),
+            store: self.store.split_alias(),
+        }
+    }
+}
+
+impl<S> IntoJoin for Maybe<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(0..usize::max_value(), self)
+    }
+}
+
+/// Wrapper to join a negated source. Only the indices missing from the source are iterated.
+pub struct Not<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    range: Range<usize>,
+    store: S,
+}
+
+/// Create a join source that excludes the indices present in the source.
+/// The source is unbounded, thus it has to be joined with at least one bounded source.
+/// The indices of the source are skipped one by one, thus the bounding partner should be the sparser source.
+pub fn not<J: IntoJoin>(source: J) -> Not<J::Store> {
+    let (range, store) = source.into_join().into_parts();
+    Not { range, store }
+}
+
+impl<S> IndexExcl<usize> for Not<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    type Item = ();
+
+    fn index(&mut self, _idx: usize) -> Self::Item {}
+}
+
+impl<S> IndexLowerBound<usize> for Not<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    fn lower_bound(&mut self, mut idx: usize) -> Option<usize> {
+        while idx >= self.range.start && idx < self.range.end && self.store.lower_bound(idx) == Some(idx) {
+            idx += 1;
+        }
+        Some(idx)
+    }
+}
+
+impl<S> IndexRead for Not<S> where S: IndexLowerBound<usize> {}
+
+unsafe impl<S> IndexSplit for Not<S>
+where
+    S: IndexSplit,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        Not {
+            range: self.range.clone(),
+            store: self.store.split_alias(),
+        }
+    }
+}
+
+impl<S> IntoJoin for Not<S>
+where
+    S: IndexLowerBound<usize>,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(0..usize::max_value(), self)
+    }
+}
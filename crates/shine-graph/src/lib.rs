@@ -1,5 +1,9 @@
+pub mod algo;
 pub mod bits;
 pub mod join;
+pub mod linalg;
+#[cfg(feature = "parallel")]
+pub mod parjoin;
 pub mod smat;
 pub mod svec;
 pub mod traits;
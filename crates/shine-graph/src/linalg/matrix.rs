@@ -0,0 +1,232 @@
+use crate::bits::{bitops, BitSetViewExt};
+use crate::smat::{DataRange, MatrixMask, SMatrix, Store as MatStore, StoreMut as MatStoreMut};
+use crate::svec::{SVector, Store as VecStore, StoreMut as VecStoreMut};
+use num_traits::Zero;
+use std::collections::BTreeMap;
+use std::ops::Mul;
+
+/// Call f for each non-zero item of a row with the column index and the item
+fn for_each_in_row<'a, M, S, F>(a: &'a SMatrix<M, S>, r: usize, mut f: F)
+where
+    M: MatrixMask,
+    S: MatStore,
+    F: FnMut(usize, &'a S::Item),
+{
+    let DataRange(start, end) = a.mask.get_data_range(r);
+    for pos in start..end {
+        f(a.mask.get_column_index(pos.into()), a.store.get(pos));
+    }
+}
+
+/// Sparse matrix - sparse vector product: y = A * x.
+/// Only the rows with at least one matching non-zero item are stored.
+pub fn mat_vec_mul<T, M, SA, SX, SY>(a: &SMatrix<M, SA>, x: &SVector<SX>, y: &mut SVector<SY>)
+where
+    T: Zero + Mul<Output = T> + Clone,
+    M: MatrixMask,
+    SA: MatStore<Item = T>,
+    SX: VecStore<Item = T>,
+    SY: VecStoreMut<Item = T>,
+{
+    y.clear();
+    for r in a.row_mask.iter() {
+        let mut sum: Option<T> = None;
+        for_each_in_row(a, r, |c, v| {
+            if let Some(xc) = x.get(c) {
+                let p = v.clone() * xc.clone();
+                sum = Some(match sum.take() {
+                    Some(s) => s + p,
+                    None => p,
+                });
+            }
+        });
+        if let Some(sum) = sum {
+            y.add(r, sum);
+        }
+    }
+}
+
+/// Sparse vector - sparse matrix product: y = x^T * A.
+/// The non-zero items of x are propagated along the rows of A (ex. along the outgoing edges).
+pub fn vec_mat_mul<T, M, SA, SX, SY>(x: &SVector<SX>, a: &SMatrix<M, SA>, y: &mut SVector<SY>)
+where
+    T: Zero + Mul<Output = T> + Clone,
+    M: MatrixMask,
+    SA: MatStore<Item = T>,
+    SX: VecStore<Item = T>,
+    SY: VecStoreMut<Item = T>,
+{
+    y.clear();
+    for r in x.mask.iter() {
+        if !a.row_mask.get(r) {
+            continue;
+        }
+        let xr = x.get_unchecked(r);
+        for_each_in_row(a, r, |c, v| {
+            let p = xr.clone() * v.clone();
+            match y.get_mut(c) {
+                Some(yc) => *yc = yc.clone() + p,
+                None => {
+                    y.add(c, p);
+                }
+            }
+        });
+    }
+}
+
+/// Sparse matrix - sparse matrix product: C = A * B.
+pub fn mat_mat_mul<T, MA, SA, MB, SB, MC, SC>(a: &SMatrix<MA, SA>, b: &SMatrix<MB, SB>, c: &mut SMatrix<MC, SC>)
+where
+    T: Zero + Mul<Output = T> + Clone,
+    MA: MatrixMask,
+    SA: MatStore<Item = T>,
+    MB: MatrixMask,
+    SB: MatStore<Item = T>,
+    MC: MatrixMask,
+    SC: MatStoreMut<Item = T>,
+{
+    c.clear();
+    let mut row = BTreeMap::new();
+    for r in a.row_mask.iter() {
+        for_each_in_row(a, r, |k, ark| {
+            if !b.row_mask.get(k) {
+                return;
+            }
+            for_each_in_row(b, k, |j, bkj| {
+                let p = ark.clone() * bkj.clone();
+                let acc = row.entry(j).or_insert_with(T::zero);
+                *acc = acc.clone() + p;
+            });
+        });
+        for (j, v) in row.iter() {
+            c.add(r, *j, v.clone());
+        }
+        row.clear();
+    }
+}
+
+/// Transpose of a matrix: T = A^T.
+pub fn transpose<T, MA, SA, MT, ST>(a: &SMatrix<MA, SA>, t: &mut SMatrix<MT, ST>)
+where
+    T: Clone,
+    MA: MatrixMask,
+    SA: MatStore<Item = T>,
+    MT: MatrixMask,
+    ST: MatStoreMut<Item = T>,
+{
+    t.clear();
+    // collect the items in the row order of the result, thus each insertion appends to the end of the data
+    let mut items = Vec::with_capacity(a.nnz());
+    for r in a.row_mask.iter() {
+        for_each_in_row(a, r, |c, v| items.push((c, r, v)));
+    }
+    items.sort_unstable_by_key(|&(c, r, _)| (c, r));
+    for (c, r, v) in items {
+        t.add(c, r, v.clone());
+    }
+}
+
+/// Elementwise sum of two matrices with mask union semantics: C = A + B.
+/// Items present only in one of the matrices are copied.
+pub fn mat_add<T, MA, SA, MB, SB, MC, SC>(a: &SMatrix<MA, SA>, b: &SMatrix<MB, SB>, c: &mut SMatrix<MC, SC>)
+where
+    T: Zero + Clone,
+    MA: MatrixMask,
+    SA: MatStore<Item = T>,
+    MB: MatrixMask,
+    SB: MatStore<Item = T>,
+    MC: MatrixMask,
+    SC: MatStoreMut<Item = T>,
+{
+    c.clear();
+    for r in bitops::or2(&a.row_mask, &b.row_mask).iter() {
+        // merge the sorted rows
+        let DataRange(mut pa, ea) = a.mask.get_data_range(r);
+        let DataRange(mut pb, eb) = b.mask.get_data_range(r);
+        loop {
+            let ja = if pa < ea {
+                Some(a.mask.get_column_index(pa.into()))
+            } else {
+                None
+            };
+            let jb = if pb < eb {
+                Some(b.mask.get_column_index(pb.into()))
+            } else {
+                None
+            };
+            match (ja, jb) {
+                (Some(i), Some(j)) if i == j => {
+                    c.add(r, i, a.store.get(pa).clone() + b.store.get(pb).clone());
+                    pa += 1;
+                    pb += 1;
+                }
+                (Some(i), Some(j)) if i < j => {
+                    c.add(r, i, a.store.get(pa).clone());
+                    pa += 1;
+                }
+                (_, Some(j)) => {
+                    c.add(r, j, b.store.get(pb).clone());
+                    pb += 1;
+                }
+                (Some(i), None) => {
+                    c.add(r, i, a.store.get(pa).clone());
+                    pa += 1;
+                }
+                (None, None) => break,
+            }
+        }
+    }
+}
+
+/// Elementwise product of two matrices with mask intersection semantics: C = A .* B.
+pub fn mat_elem_mul<T, MA, SA, MB, SB, MC, SC>(a: &SMatrix<MA, SA>, b: &SMatrix<MB, SB>, c: &mut SMatrix<MC, SC>)
+where
+    T: Mul<Output = T> + Clone,
+    MA: MatrixMask,
+    SA: MatStore<Item = T>,
+    MB: MatrixMask,
+    SB: MatStore<Item = T>,
+    MC: MatrixMask,
+    SC: MatStoreMut<Item = T>,
+{
+    c.clear();
+    for r in a.row_mask.iter() {
+        if !b.row_mask.get(r) {
+            continue;
+        }
+        for_each_in_row(a, r, |j, v| {
+            if let Some(w) = b.get(r, j) {
+                c.add(r, j, v.clone() * w.clone());
+            }
+        });
+    }
+}
+
+/// Apply a function on each non-zero item: C = f(A). The sparsity pattern is kept.
+pub fn mat_map<T, U, F, MA, SA, MC, SC>(a: &SMatrix<MA, SA>, mut f: F, c: &mut SMatrix<MC, SC>)
+where
+    F: FnMut(&T) -> U,
+    MA: MatrixMask,
+    SA: MatStore<Item = T>,
+    MC: MatrixMask,
+    SC: MatStoreMut<Item = U>,
+{
+    c.clear();
+    for r in a.row_mask.iter() {
+        for_each_in_row(a, r, |j, v| {
+            c.add(r, j, f(v));
+        });
+    }
+}
+
+/// Multiply each non-zero item by a scalar in place: A = alpha * A.
+pub fn mat_scale<T, M, S>(a: &mut SMatrix<M, S>, alpha: T)
+where
+    T: Mul<Output = T> + Clone,
+    M: MatrixMask,
+    S: MatStoreMut<Item = T>,
+{
+    for v in a.data_iter_mut() {
+        *v = alpha.clone() * v.clone();
+    }
+}
@@ -0,0 +1,5 @@
+mod matrix;
+mod vector;
+
+pub use self::matrix::*;
+pub use self::vector::*;
@@ -0,0 +1,111 @@
+use crate::bits::BitSetViewExt;
+use crate::svec::{SVector, Store, StoreMut};
+use num_traits::Zero;
+use std::ops::Mul;
+
+/// Elementwise sum of two vectors with mask union semantics: y = a + b.
+/// Items present only in one of the vectors are copied.
+pub fn vec_add<T, SA, SB, SY>(a: &SVector<SA>, b: &SVector<SB>, y: &mut SVector<SY>)
+where
+    T: Zero + Clone,
+    SA: Store<Item = T>,
+    SB: Store<Item = T>,
+    SY: StoreMut<Item = T>,
+{
+    y.clear();
+    let mut ia = a.mask.lower_bound(0);
+    let mut ib = b.mask.lower_bound(0);
+    loop {
+        match (ia, ib) {
+            (Some(i), Some(j)) if i == j => {
+                y.add(i, a.get_unchecked(i).clone() + b.get_unchecked(j).clone());
+                ia = a.mask.lower_bound(i + 1);
+                ib = b.mask.lower_bound(j + 1);
+            }
+            (Some(i), Some(j)) if i < j => {
+                y.add(i, a.get_unchecked(i).clone());
+                ia = a.mask.lower_bound(i + 1);
+            }
+            (_, Some(j)) => {
+                y.add(j, b.get_unchecked(j).clone());
+                ib = b.mask.lower_bound(j + 1);
+            }
+            (Some(i), None) => {
+                y.add(i, a.get_unchecked(i).clone());
+                ia = a.mask.lower_bound(i + 1);
+            }
+            (None, None) => break,
+        }
+    }
+}
+
+/// Elementwise product of two vectors with mask intersection semantics: y = a .* b.
+pub fn vec_elem_mul<T, SA, SB, SY>(a: &SVector<SA>, b: &SVector<SB>, y: &mut SVector<SY>)
+where
+    T: Mul<Output = T> + Clone,
+    SA: Store<Item = T>,
+    SB: Store<Item = T>,
+    SY: StoreMut<Item = T>,
+{
+    y.clear();
+    let mut ia = a.mask.lower_bound(0);
+    while let Some(i) = ia {
+        match b.mask.lower_bound(i) {
+            Some(j) if i == j => {
+                y.add(i, a.get_unchecked(i).clone() * b.get_unchecked(j).clone());
+                ia = a.mask.lower_bound(i + 1);
+            }
+            Some(j) => ia = a.mask.lower_bound(j),
+            None => break,
+        }
+    }
+}
+
+/// Dot product of two vectors.
+pub fn vec_dot<T, SA, SB>(a: &SVector<SA>, b: &SVector<SB>) -> T
+where
+    T: Zero + Mul<Output = T> + Clone,
+    SA: Store<Item = T>,
+    SB: Store<Item = T>,
+{
+    let mut sum = T::zero();
+    let mut ia = a.mask.lower_bound(0);
+    while let Some(i) = ia {
+        match b.mask.lower_bound(i) {
+            Some(j) if i == j => {
+                sum = sum + a.get_unchecked(i).clone() * b.get_unchecked(j).clone();
+                ia = a.mask.lower_bound(i + 1);
+            }
+            Some(j) => ia = a.mask.lower_bound(j),
+            None => break,
+        }
+    }
+    sum
+}
+
+/// Apply a function on each non-zero item: y = f(x). The sparsity pattern is kept.
+pub fn vec_map<T, U, F, SX, SY>(x: &SVector<SX>, mut f: F, y: &mut SVector<SY>)
+where
+    F: FnMut(&T) -> U,
+    SX: Store<Item = T>,
+    SY: StoreMut<Item = U>,
+{
+    y.clear();
+    for i in x.mask.iter() {
+        y.add(i, f(x.get_unchecked(i)));
+    }
+}
+
+/// Multiply each non-zero item by a scalar in place: x = alpha * x.
+pub fn vec_scale<T, S>(x: &mut SVector<S>, alpha: T)
+where
+    T: Mul<Output = T> + Clone,
+    S: StoreMut<Item = T>,
+{
+    let mut next = x.mask.lower_bound(0);
+    while let Some(i) = next {
+        let v = x.get_mut_unchecked(i);
+        *v = alpha.clone() * v.clone();
+        next = x.mask.lower_bound(i + 1);
+    }
+}
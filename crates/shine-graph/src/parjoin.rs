@@ -0,0 +1,96 @@
+use crate::bits::BitBlock;
+use crate::join::{IntoJoin, Join};
+use crate::svec::VectorMaskBlock;
+use crate::traits::{IndexExcl, IndexSplit};
+use std::ops::Range;
+
+/// Return the default chunk size of the parallel joins.
+/// A chunk covers the bits of a second level block of the VectorMask, thus
+/// the empty chunks are skipped by checking a single bit of the hierarchy.
+pub fn default_chunk_size() -> usize {
+    1 << (2 * VectorMaskBlock::bit_shift())
+}
+
+/// Process the items of the range in chunks. The range is halved (on chunk boundary)
+/// until a single chunk remains and the halves are processed on the rayon thread pool.
+fn par_process<S, F>(mut store: S, range: Range<usize>, chunk_size: usize, f: &F)
+where
+    S: IndexSplit + Send,
+    F: Fn(usize, <S as IndexExcl<usize>>::Item) + Sync,
+{
+    let start = match store.lower_bound(range.start) {
+        Some(idx) if idx < range.end => idx,
+        _ => return,
+    };
+
+    let mid = start + (range.end - start) / 2;
+    let mid = (mid + chunk_size - 1) / chunk_size * chunk_size;
+    if mid <= start || mid >= range.end {
+        let mut next = Some(start);
+        while let Some(idx) = next {
+            if idx >= range.end {
+                break;
+            }
+            f(idx, store.index(idx));
+            next = store.lower_bound(idx + 1);
+        }
+    } else {
+        // alias is safe as the two halves are disjoint
+        let alias = unsafe { store.split_alias() };
+        rayon::join(
+            move || par_process(store, start..mid, chunk_size, f),
+            move || par_process(alias, mid..range.end, chunk_size, f),
+        );
+    }
+}
+
+impl<S> Join<S>
+where
+    S: IndexSplit + Send,
+{
+    /// Call f for each remaining item on the rayon thread pool.
+    /// Items are processed in an arbitrary order.
+    pub fn par_for_each<F>(self, f: F)
+    where
+        F: Fn(usize, S::Item) + Sync,
+    {
+        self.par_for_each_chunked(default_chunk_size(), f);
+    }
+
+    /// Call f for each remaining item on the rayon thread pool. The range is split
+    /// into chunks of chunk_size (rounded up to the VectorMask block size) and a chunk is never split
+    /// among threads.
+    pub fn par_for_each_chunked<F>(self, chunk_size: usize, f: F)
+    where
+        F: Fn(usize, S::Item) + Sync,
+    {
+        let block_size = VectorMaskBlock::bit_count();
+        let chunk_size = ((chunk_size.max(1) + block_size - 1) / block_size) * block_size;
+        let (range, store) = self.into_parts();
+        assert!(
+            range.start >= range.end || range.end != usize::max_value(),
+            "Join has no bounded source, maybe and not require a bounding partner"
+        );
+        par_process(store, range, chunk_size, &f);
+    }
+}
+
+pub trait IntoParJoinExt: IntoJoin
+where
+    Self::Store: IndexSplit + Send,
+{
+    fn par_join_all<F>(self, f: F)
+    where
+        F: Fn(usize, <Self::Store as IndexExcl<usize>>::Item) + Sync,
+        Self: Sized,
+    {
+        self.into_join().par_for_each(f);
+    }
+}
+
+impl<T: ?Sized> IntoParJoinExt for T
+where
+    T: IntoJoin,
+    T::Store: IndexSplit + Send,
+{
+}
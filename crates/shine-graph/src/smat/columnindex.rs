@@ -0,0 +1,58 @@
+use crate::bits::BitSetViewExt;
+use crate::smat::{DataRange, MatrixMask};
+use crate::svec::VectorMask;
+
+/// Transposed mask of an SMatrix to find the rows of the non-zero items in a column.
+/// The index stores the (column, row) pairs, the data itself is accessed through the row mask.
+pub struct ColumnIndex<M>
+where
+    M: MatrixMask,
+{
+    pub(crate) column_mask: VectorMask,
+    pub(crate) mask: M,
+}
+
+impl<M> ColumnIndex<M>
+where
+    M: MatrixMask,
+{
+    pub fn new(mut mask: M) -> ColumnIndex<M> {
+        mask.clear();
+        ColumnIndex {
+            column_mask: VectorMask::new(),
+            mask,
+        }
+    }
+
+    /// Return the column capacity.
+    pub fn capacity(&self) -> usize {
+        self.column_mask.capacity()
+    }
+
+    /// Return if the column has any non-zero items.
+    pub fn contains_column(&self, c: usize) -> bool {
+        self.column_mask.get(c)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.mask.clear();
+        self.column_mask.clear();
+    }
+
+    pub(crate) fn add(&mut self, r: usize, c: usize) {
+        self.mask.add(c, r);
+        self.column_mask.add(c);
+    }
+
+    pub(crate) fn remove(&mut self, r: usize, c: usize) {
+        if let Some((_, DataRange(start, end))) = self.mask.remove(c, r) {
+            if start == end {
+                self.column_mask.remove(c);
+            }
+        }
+    }
+
+    pub(crate) fn get_data_range(&self, c: usize) -> DataRange {
+        self.mask.get_data_range(c)
+    }
+}
@@ -0,0 +1,230 @@
+use crate::bits::BitSetViewExt;
+use crate::join::{IntoJoin, Join};
+use crate::smat::{ColumnIndex, DataPosition, DataRange, MatrixMask, MatrixMaskExt, SMatrix, Store, StoreMut, UpdateAccess};
+use crate::traits::{IndexExcl, IndexLowerBound, IndexRead};
+
+/// Access a single column in the matrix.
+pub struct ColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    pub(crate) column: usize,
+    pub(crate) mask: &'a M,
+    pub(crate) column_mask: &'a M,
+    pub(crate) store: &'a S,
+    pub(crate) data_range: DataRange,
+    pub(crate) cursor: DataPosition,
+}
+
+impl<'a, M, S> IndexExcl<usize> for ColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Item = &'a S::Item;
+
+    fn index(&mut self, idx: usize) -> Self::Item {
+        let DataPosition(pos) = self.mask.get_data_position(idx, self.column).unwrap();
+        self.store.get(pos)
+    }
+}
+
+impl<'a, M, S> IndexLowerBound<usize> for ColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    fn lower_bound(&mut self, idx: usize) -> Option<usize> {
+        self.column_mask
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
+            .map(|(idx, _)| idx)
+    }
+}
+
+impl<'a, M, S> IntoJoin for ColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(self.column_mask.get_column_range(self.data_range), self)
+    }
+}
+
+impl<'a, M, S> IndexRead for ColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+}
+
+/// Access a single column in the matrix.
+pub struct ColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    pub(crate) column: usize,
+    pub(crate) mask: &'a M,
+    pub(crate) column_mask: &'a M,
+    pub(crate) store: UpdateAccess<'a, S>,
+    pub(crate) data_range: DataRange,
+    pub(crate) cursor: DataPosition,
+}
+
+impl<'a, M, S> IndexExcl<usize> for ColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    type Item = &'a mut <S as Store>::Item;
+
+    fn index(&mut self, idx: usize) -> Self::Item {
+        let DataPosition(pos) = self.mask.get_data_position(idx, self.column).unwrap();
+        unsafe { self.store.get_mut(pos) }
+    }
+}
+
+impl<'a, M, S> IndexLowerBound<usize> for ColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    fn lower_bound(&mut self, idx: usize) -> Option<usize> {
+        self.column_mask
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
+            .map(|(idx, _)| idx)
+    }
+}
+
+impl<'a, M, S> IntoJoin for ColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(self.column_mask.get_column_range(self.data_range), self)
+    }
+}
+
+/// Wrapper to allow immutable access to the elments of an SMatrix in column-major order. Used for join and merge oprations.
+pub struct WrapColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    pub(crate) mat: &'a SMatrix<M, S>,
+}
+
+impl<'a, M, S> IndexExcl<usize> for WrapColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Item = ColumnRead<'a, M, S>;
+
+    fn index(&mut self, idx: usize) -> Self::Item {
+        self.mat.read_column(idx)
+    }
+}
+
+impl<'a, M, S> IndexLowerBound<usize> for WrapColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    fn lower_bound(&mut self, idx: usize) -> Option<usize> {
+        self.mat.column_index().column_mask.lower_bound(idx)
+    }
+}
+
+impl<'a, M, S> IntoJoin for WrapColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(0..self.mat.column_index().capacity(), self)
+    }
+}
+
+impl<'a, M, S> IndexRead for WrapColumnRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+}
+
+/// Wrapper to allow mutable access to the elments of an SMatrix in column-major order. Used for join and merge oprations.
+pub struct WrapColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    pub(crate) mask: &'a M,
+    pub(crate) column_index: &'a ColumnIndex<M>,
+    pub(crate) store: UpdateAccess<'a, S>,
+}
+
+impl<'a, M, S> WrapColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    pub(crate) fn new(mat: &'a mut SMatrix<M, S>) -> Self {
+        WrapColumnUpdate {
+            mask: &mat.mask,
+            column_index: mat.column_index.as_ref().expect("Column index is not enabled"),
+            store: UpdateAccess::new(&mut mat.store),
+        }
+    }
+}
+
+impl<'a, M, S> IndexExcl<usize> for WrapColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    type Item = ColumnUpdate<'a, M, S>;
+
+    fn index(&mut self, idx: usize) -> Self::Item {
+        let data_range = self.column_index.get_data_range(idx);
+        ColumnUpdate {
+            column: idx,
+            mask: self.mask,
+            column_mask: &self.column_index.mask,
+            store: unsafe { self.store.alias() },
+            data_range,
+            cursor: DataPosition(data_range.0),
+        }
+    }
+}
+
+impl<'a, M, S> IndexLowerBound<usize> for WrapColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    fn lower_bound(&mut self, idx: usize) -> Option<usize> {
+        self.column_index.column_mask.lower_bound(idx)
+    }
+}
+
+impl<'a, M, S> IntoJoin for WrapColumnUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    type Store = Self;
+
+    fn into_join(self) -> Join<Self::Store> {
+        Join::from_parts(0..self.column_index.capacity(), self)
+    }
+}
@@ -139,6 +139,25 @@ impl MatrixMask for CSMatrixMask {
         }
     }
 
+    fn gallop_lower_bound_column_position(
+        &self,
+        column: usize,
+        range: DataRange,
+        hint: DataPosition,
+    ) -> Option<(usize, DataPosition)> {
+        let DataRange(range_start, range_end) = range;
+        if range_start >= range_end {
+            return None;
+        }
+        let hint = usize::from(hint).max(range_start) - range_start;
+        let pos = self.indices[range_start..range_end].gallop_lower_bound(hint, &column) + range_start;
+        if pos < range_end {
+            Some((self.indices[pos], pos.into()))
+        } else {
+            None
+        }
+    }
+
     fn get_column_index(&self, pos: DataPosition) -> usize {
         self.indices[usize::from(pos)]
     }
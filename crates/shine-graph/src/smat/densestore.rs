@@ -1,4 +1,4 @@
-use crate::smat::{Store, StoreMut};
+use crate::smat::{DisjointStoreMut, Store, StoreMut};
 use std::mem;
 
 pub struct DenseStore<T> {
@@ -51,3 +51,13 @@ impl<T> StoreMut for DenseStore<T> {
         &mut self.values[idx]
     }
 }
+
+unsafe impl<T> DisjointStoreMut for DenseStore<T> {
+    fn base_ptr(&mut self) -> *mut u8 {
+        self.values.as_mut_ptr() as *mut u8
+    }
+
+    unsafe fn get_mut_ptr(_this: *const Self, base: *mut u8, idx: usize) -> *mut Self::Item {
+        (base as *mut T).add(idx)
+    }
+}
@@ -1,20 +1,31 @@
 use crate::smat::{DataPosition, DataRange, MatrixMask};
-use std::collections::HashMap;
+use log::trace;
+use shine_stdext::stdext::SliceOrdExt;
 
 /// Compressed Sparse (Square) Row matrix.
-/// Its a variant of the CSR data structure where a HashMap is
-///  used to store the data ranges for the occupied rows.
-#[allow(dead_code)]
+/// Its a variant of the CSR data structure for very tall and sparse matrices where only
+///  the occupied rows are stored, a row is found by a binary search.
+/// The data of the rows are stored in the order of the row index, thus the
+/// flattened array has the same layout as for the CSMatrixMask.
+/// Adding or removing an item shifts the data ranges of the subsequent occupied rows.
 pub struct HCSMatrixMask {
-    offsets: HashMap<usize, DataRange>,
+    // Sorted list of the occupied rows
+    rows: Vec<usize>,
+
+    // End of the data range in the indices(data) vector for each occupied row,
+    // a row starts where the previous one ends
+    ends: Vec<usize>,
+
+    // Column indices for each non-zero items
     indices: Vec<usize>,
 }
 
 impl HCSMatrixMask {
-    /// Creates a new HCSMatrixMask with the given
+    /// Creates a new HCSMatrixMask with the given capacity
     pub fn new_with_capacity(nnz_capacity: usize) -> HCSMatrixMask {
         HCSMatrixMask {
-            offsets: HashMap::new(), //new_with_capacity(nnz_capacity),
+            rows: Vec::new(),
+            ends: Vec::new(),
             indices: Vec::with_capacity(nnz_capacity),
         }
     }
@@ -23,6 +34,32 @@ impl HCSMatrixMask {
     pub fn new() -> HCSMatrixMask {
         Self::new_with_capacity(0)
     }
+
+    /// Return the number of non-zero items.
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Return the number of the occupied rows.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return the data range of the row at the given position.
+    fn row_range(&self, position: usize) -> DataRange {
+        let start = if position == 0 { 0 } else { self.ends[position - 1] };
+        DataRange(start, self.ends[position])
+    }
+
+    /// Return the position of an occupied row, or the position where the row would be inserted.
+    fn find_row(&self, row: usize) -> Result<usize, usize> {
+        let position = self.rows.lower_bound(&row);
+        if position < self.rows.len() && self.rows[position] == row {
+            Ok(position)
+        } else {
+            Err(position)
+        }
+    }
 }
 
 impl Default for HCSMatrixMask {
@@ -34,26 +71,101 @@ impl Default for HCSMatrixMask {
 impl MatrixMask for HCSMatrixMask {
     fn clear(&mut self) {
         self.indices.clear();
-        self.offsets.clear();
+        self.rows.clear();
+        self.ends.clear();
+    }
+
+    fn add(&mut self, row: usize, column: usize) -> (DataPosition, bool) {
+        let (position, pos) = match self.find_row(row) {
+            Ok(position) => {
+                let DataRange(idx0, idx1) = self.row_range(position);
+                let pos = self.indices[idx0..idx1].lower_bound(&column) + idx0;
+                if pos < idx1 && self.indices[pos] == column {
+                    trace!("item replaced at: {}", pos);
+                    return (DataPosition(pos), true);
+                }
+                (position, pos)
+            }
+            Err(position) => {
+                trace!("new row opened: {}", row);
+                // the new row starts where the previous occupied row ends
+                let start = if position == 0 { 0 } else { self.ends[position - 1] };
+                self.rows.insert(position, row);
+                self.ends.insert(position, start);
+                (position, start)
+            }
+        };
+
+        trace!("item added at: {}", pos);
+        self.indices.insert(pos, column);
+        for end in &mut self.ends[position..] {
+            *end += 1;
+        }
+        (DataPosition(pos), false)
     }
 
-    fn add(&mut self, _major: usize, _minor: usize) -> (DataPosition, bool) {
-        unimplemented!()
+    fn remove(&mut self, row: usize, column: usize) -> Option<(DataPosition, DataRange)> {
+        let position = self.find_row(row).ok()?;
+        let DataRange(idx0, idx1) = self.row_range(position);
+        let pos = self.indices[idx0..idx1].lower_bound(&column) + idx0;
+        if pos >= idx1 || self.indices[pos] != column {
+            return None;
+        }
+
+        trace!("item removed at: {}", pos);
+        self.indices.remove(pos);
+        for end in &mut self.ends[position..] {
+            *end -= 1;
+        }
+        if idx0 == idx1 - 1 {
+            trace!("row closed: {}", row);
+            self.rows.remove(position);
+            self.ends.remove(position);
+        }
+        Some((DataPosition(pos), DataRange(idx0, idx1 - 1)))
     }
 
-    fn remove(&mut self, _major: usize, _minor: usize) -> Option<(DataPosition, DataRange)> {
-        unimplemented!()
+    fn get_data_range(&self, row: usize) -> DataRange {
+        match self.find_row(row) {
+            Ok(position) => self.row_range(position),
+            // return an empty range
+            Err(_) => DataRange(usize::max_value(), usize::max_value()),
+        }
     }
 
-    fn get_data_range(&self, _major: usize) -> DataRange {
-        unimplemented!()
+    fn lower_bound_column_position(&self, column: usize, range: DataRange) -> Option<(usize, DataPosition)> {
+        let DataRange(range_start, range_end) = range;
+        if range_start >= range_end {
+            return None;
+        }
+        let pos = self.indices[range_start..range_end].lower_bound(&column) + range_start;
+        if pos < range_end {
+            Some((self.indices[pos], pos.into()))
+        } else {
+            None
+        }
     }
 
-    fn lower_bound_column_position(&self, _column: usize, _range: DataRange) -> Option<(usize, DataPosition)> {
-        unimplemented!()
+    fn gallop_lower_bound_column_position(
+        &self,
+        column: usize,
+        range: DataRange,
+        hint: DataPosition,
+    ) -> Option<(usize, DataPosition)> {
+        let DataRange(range_start, range_end) = range;
+        if range_start >= range_end {
+            return None;
+        }
+        let hint = usize::from(hint).max(range_start) - range_start;
+        let pos = self.indices[range_start..range_end].gallop_lower_bound(hint, &column) + range_start;
+        if pos < range_end {
+            Some((self.indices[pos], pos.into()))
+        } else {
+            None
+        }
     }
 
-    fn get_column_index(&self, _pos: DataPosition) -> usize {
-        unimplemented!()
+    fn get_column_index(&self, pos: DataPosition) -> usize {
+        self.indices[usize::from(pos)]
     }
 }
@@ -1,7 +1,10 @@
 use crate::bits::BitSetViewExt;
 use crate::join::{IntoJoin, Join};
-use crate::smat::{MatrixMask, RowRead, RowUpdate, RowWrite, SMatrix, Store, StoreMut};
-use crate::traits::{IndexExcl, IndexLowerBound};
+use crate::smat::{
+    DataPosition, DisjointStoreMut, MatrixMask, RowRead, RowUpdate, RowWrite, SMatrix, Store, StoreMut, UpdateAccess,
+};
+use crate::svec::VectorMask;
+use crate::traits::{IndexExcl, IndexLowerBound, IndexRead, IndexSplit};
 use std::mem;
 
 /// Wrapper to allow immutable access to the elments of an SMatrix in row-major order. Used for join and merge oprations.
@@ -47,13 +50,46 @@ where
     }
 }
 
+impl<'a, M, S> IndexRead for WrapRowRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+}
+
+unsafe impl<'a, M, S> IndexSplit for WrapRowRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        WrapRowRead { mat: self.mat }
+    }
+}
+
 /// Wrapper to allow mutable access to the elments of an SMatrix in row-major order. Used for join and merge oprations.
 pub struct WrapRowUpdate<'a, M, S>
 where
     M: MatrixMask,
-    S: Store,
+    S: StoreMut,
 {
-    pub(crate) mat: &'a mut SMatrix<M, S>,
+    pub(crate) row_mask: &'a VectorMask,
+    pub(crate) mask: &'a M,
+    pub(crate) store: UpdateAccess<'a, S>,
+}
+
+impl<'a, M, S> WrapRowUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: StoreMut,
+{
+    pub(crate) fn new(mat: &'a mut SMatrix<M, S>) -> Self {
+        WrapRowUpdate {
+            row_mask: &mat.row_mask,
+            mask: &mat.mask,
+            store: UpdateAccess::new(&mut mat.store),
+        }
+    }
 }
 
 impl<'a, M, S> IndexExcl<usize> for WrapRowUpdate<'a, M, S>
@@ -64,7 +100,13 @@ where
     type Item = RowUpdate<'a, M, S>;
 
     fn index(&mut self, idx: usize) -> Self::Item {
-        unsafe { mem::transmute(self.mat.update_row(idx)) } //GAT
+        let data_range = self.mask.get_data_range(idx);
+        RowUpdate {
+            mask: self.mask,
+            store: unsafe { self.store.alias() },
+            data_range,
+            cursor: DataPosition(data_range.0),
+        }
     }
 }
 
@@ -74,7 +116,7 @@ where
     S: StoreMut,
 {
     fn lower_bound(&mut self, idx: usize) -> Option<usize> {
-        self.mat.row_mask.lower_bound(idx)
+        self.row_mask.lower_bound(idx)
     }
 }
 
@@ -86,7 +128,21 @@ where
     type Store = Self;
 
     fn into_join(self) -> Join<Self::Store> {
-        Join::from_parts(0..self.mat.capacity(), self)
+        Join::from_parts(0..self.row_mask.capacity(), self)
+    }
+}
+
+unsafe impl<'a, M, S> IndexSplit for WrapRowUpdate<'a, M, S>
+where
+    M: MatrixMask,
+    S: DisjointStoreMut + Sync,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        WrapRowUpdate {
+            row_mask: self.row_mask,
+            mask: self.mask,
+            store: self.store.split_alias(),
+        }
     }
 }
 
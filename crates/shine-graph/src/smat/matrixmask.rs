@@ -51,6 +51,15 @@ pub trait MatrixMask {
     /// Find the first valid column index and its position that is not less than the provided index.
     fn lower_bound_column_position(&self, column: usize, range: DataRange) -> Option<(usize, DataPosition)>;
 
+    /// Find the first valid column index and its position that is not less than the provided index
+    /// using a galloping search starting at the hint position.
+    fn gallop_lower_bound_column_position(
+        &self,
+        column: usize,
+        range: DataRange,
+        hint: DataPosition,
+    ) -> Option<(usize, DataPosition)>;
+
     /// Gets the column index of item stored at the given position in the flattend array.
     fn get_column_index(&self, pos: DataPosition) -> usize;
 }
@@ -88,5 +97,15 @@ pub trait MatrixMaskExt: MatrixMask {
             None => None,
         }
     }
+
+    /// Find the first valid column index that is not less than the provided index starting the search at the cursor
+    /// and move the cursor to the found position. Joins query increasing indices, thus the search distance is short.
+    fn seek_column_position(&self, column: usize, range: DataRange, cursor: &mut DataPosition) -> Option<(usize, DataPosition)> {
+        let found = self.gallop_lower_bound_column_position(column, range, *cursor);
+        if let Some((_, pos)) = found {
+            *cursor = pos;
+        }
+        found
+    }
 }
 impl<T: ?Sized> MatrixMaskExt for T where T: MatrixMask {}
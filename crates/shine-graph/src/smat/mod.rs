@@ -1,4 +1,6 @@
 mod arenastore;
+mod columnindex;
+mod columniter;
 mod csmatrixmask;
 mod densestore;
 mod entry;
@@ -8,6 +10,8 @@ mod matrixmask;
 mod rowiter;
 mod smatrices;
 mod smatrix;
+#[cfg(feature = "serialize")]
+mod smatrixserde;
 mod store;
 mod unitstore;
 
@@ -15,6 +19,8 @@ mod unitstore;
 mod dataiter;
 /// Trait implementations to make an SVector joinable
 pub use self::arenastore::*;
+pub use self::columnindex::*;
+pub use self::columniter::*;
 pub use self::csmatrixmask::*;
 pub use self::dataiter::*;
 pub use self::densestore::*;
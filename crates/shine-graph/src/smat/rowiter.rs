@@ -1,6 +1,6 @@
 use crate::join::{IntoJoin, Join};
-use crate::smat::{DataPosition, DataRange, Entry, MatrixMask, MatrixMaskExt, SMatrix, Store, StoreMut};
-use crate::traits::{IndexExcl, IndexLowerBound};
+use crate::smat::{DataPosition, DataRange, Entry, MatrixMask, MatrixMaskExt, SMatrix, Store, StoreMut, UpdateAccess};
+use crate::traits::{IndexExcl, IndexLowerBound, IndexRead};
 use std::mem;
 
 /// Access a single row in the matrix.
@@ -13,6 +13,7 @@ where
     pub(crate) mask: &'a M,
     pub(crate) store: &'a S,
     pub(crate) data_range: DataRange,
+    pub(crate) cursor: DataPosition,
 }
 
 impl<'a, M, S> IndexExcl<usize> for RowRead<'a, M, S>
@@ -23,7 +24,11 @@ where
     type Item = &'a S::Item;
 
     fn index(&mut self, idx: usize) -> Self::Item {
-        let DataPosition(pos) = self.mask.find_column_position(idx, self.data_range).unwrap();
+        let (column, DataPosition(pos)) = self
+            .mask
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
+            .unwrap();
+        assert_eq!(column, idx);
         self.store.get(pos)
     }
 }
@@ -35,7 +40,7 @@ where
 {
     fn lower_bound(&mut self, idx: usize) -> Option<usize> {
         self.mask
-            .lower_bound_column_position(idx, self.data_range)
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
             .map(|(idx, _)| idx)
     }
 }
@@ -52,6 +57,13 @@ where
     }
 }
 
+impl<'a, M, S> IndexRead for RowRead<'a, M, S>
+where
+    M: MatrixMask,
+    S: Store,
+{
+}
+
 /// Access a single row in the matrix.
 pub struct RowUpdate<'a, M, S>
 where
@@ -60,8 +72,9 @@ where
 {
     //crate row_index: usize,
     pub(crate) mask: &'a M,
-    pub(crate) store: &'a mut S,
+    pub(crate) store: UpdateAccess<'a, S>,
     pub(crate) data_range: DataRange,
+    pub(crate) cursor: DataPosition,
 }
 
 impl<'a, M, S> IndexExcl<usize> for RowUpdate<'a, M, S>
@@ -72,8 +85,12 @@ where
     type Item = &'a mut <S as Store>::Item;
 
     fn index(&mut self, idx: usize) -> Self::Item {
-        let DataPosition(pos) = self.mask.find_column_position(idx, self.data_range).unwrap();
-        unsafe { mem::transmute(self.store.get_mut(pos)) } // GAT
+        let (column, DataPosition(pos)) = self
+            .mask
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
+            .unwrap();
+        assert_eq!(column, idx);
+        unsafe { self.store.get_mut(pos) }
     }
 }
 
@@ -83,9 +100,8 @@ where
     S: StoreMut,
 {
     fn lower_bound(&mut self, idx: usize) -> Option<usize> {
-        //perf: we could also increment data range if it's guranted that no "step" occures.
         self.mask
-            .lower_bound_column_position(idx, self.data_range)
+            .seek_column_position(idx, self.data_range, &mut self.cursor)
             .map(|(idx, _)| idx)
     }
 }
@@ -1,7 +1,8 @@
 use crate::bits::BitSetViewExt;
 use crate::smat::{
-    DataIter, DataIterMut, DataPosition, DataRange, Entry, MatrixMask, MatrixMaskExt, RowRead, RowUpdate, RowWrite, Store,
-    StoreMut, WrapRowRead, WrapRowUpdate, WrapRowWrite,
+    ColumnIndex, ColumnRead, ColumnUpdate, DataIter, DataIterMut, DataPosition, DataRange, Entry, MatrixMask, MatrixMaskExt,
+    RowRead, RowUpdate, RowWrite, Store, StoreMut, UpdateAccess, WrapColumnRead, WrapColumnUpdate, WrapRowRead, WrapRowUpdate,
+    WrapRowWrite,
 };
 use crate::svec::VectorMask;
 
@@ -15,6 +16,7 @@ where
     pub(crate) row_mask: VectorMask,
     pub(crate) mask: M,
     pub(crate) store: S,
+    pub(crate) column_index: Option<ColumnIndex<M>>,
 }
 
 impl<M, S> SMatrix<M, S>
@@ -28,9 +30,34 @@ where
             mask,
             row_mask: VectorMask::new(),
             store,
+            column_index: None,
         }
     }
 
+    /// Maintain a transposed index to allow column based access.
+    /// The index is built from the current items and it is updated on each modification.
+    pub fn with_column_index(mut self, column_mask: M) -> Self {
+        let mut index = ColumnIndex::new(column_mask);
+        let mut row = self.row_mask.lower_bound(0);
+        while let Some(r) = row {
+            let DataRange(start, end) = self.mask.get_data_range(r);
+            for pos in start..end {
+                index.add(r, self.mask.get_column_index(pos.into()));
+            }
+            row = self.row_mask.lower_bound(r + 1);
+        }
+        self.column_index = Some(index);
+        self
+    }
+
+    pub fn has_column_index(&self) -> bool {
+        self.column_index.is_some()
+    }
+
+    pub(crate) fn column_index(&self) -> &ColumnIndex<M> {
+        self.column_index.as_ref().expect("Column index is not enabled")
+    }
+
     pub fn nnz(&self) -> usize {
         self.nnz
     }
@@ -68,6 +95,26 @@ where
             mask: &self.mask,
             store: &self.store,
             data_range,
+            cursor: DataPosition(data_range.0),
+        }
+    }
+
+    /// Immutable access in column-major order, it requires the column index.
+    pub fn read_columns(&self) -> WrapColumnRead<'_, M, S> {
+        WrapColumnRead { mat: self }
+    }
+
+    /// Immutable access of a column, it requires the column index.
+    pub fn read_column(&self, c: usize) -> ColumnRead<'_, M, S> {
+        let index = self.column_index();
+        let data_range = index.get_data_range(c);
+        ColumnRead {
+            column: c,
+            mask: &self.mask,
+            column_mask: &index.mask,
+            store: &self.store,
+            data_range,
+            cursor: DataPosition(data_range.0),
         }
     }
 }
@@ -91,6 +138,9 @@ where
         self.mask.clear();
         self.store.clear();
         self.row_mask.clear();
+        if let Some(ref mut index) = self.column_index {
+            index.clear();
+        }
         self.nnz = 0;
     }
 
@@ -101,6 +151,9 @@ where
         } else {
             self.store.insert(pos.into(), value);
             self.row_mask.add(r);
+            if let Some(ref mut index) = self.column_index {
+                index.add(r, c);
+            }
             self.nnz += 1;
             None
         }
@@ -117,6 +170,9 @@ where
                 if row_start == row_end {
                     self.row_mask.remove(r);
                 }
+                if let Some(ref mut index) = self.column_index {
+                    index.remove(r, c);
+                }
                 Some(self.store.remove(data_index.into()))
             }
             None => None,
@@ -143,7 +199,7 @@ where
     }
 
     pub fn update(&mut self) -> WrapRowUpdate<'_, M, S> {
-        WrapRowUpdate { mat: self }
+        WrapRowUpdate::new(self)
     }
 
     pub fn write(&mut self) -> WrapRowWrite<'_, M, S> {
@@ -154,14 +210,34 @@ where
         let data_range = self.mask.get_data_range(r);
         RowUpdate {
             mask: &self.mask,
-            store: &mut self.store,
+            store: UpdateAccess::new(&mut self.store),
             data_range,
+            cursor: DataPosition(data_range.0),
         }
     }
 
     pub fn write_row(&mut self, r: usize) -> RowWrite<'_, M, S> {
         RowWrite { row: r, mat: self }
     }
+
+    /// Mutable access in column-major order, it requires the column index.
+    pub fn update_columns(&mut self) -> WrapColumnUpdate<'_, M, S> {
+        WrapColumnUpdate::new(self)
+    }
+
+    /// Mutable access of a column, it requires the column index.
+    pub fn update_column(&mut self, c: usize) -> ColumnUpdate<'_, M, S> {
+        let index = self.column_index.as_ref().expect("Column index is not enabled");
+        let data_range = index.get_data_range(c);
+        ColumnUpdate {
+            column: c,
+            mask: &self.mask,
+            column_mask: &index.mask,
+            store: UpdateAccess::new(&mut self.store),
+            data_range,
+            cursor: DataPosition(data_range.0),
+        }
+    }
 }
 
 impl<T, M, S> SMatrix<M, S>
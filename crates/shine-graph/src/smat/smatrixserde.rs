@@ -0,0 +1,116 @@
+use crate::bits::{max_index_range, BitSetViewExt};
+use crate::smat::{DataRange, MatrixMask, SMatrix, Store, StoreMut};
+use crate::svec::VectorMask;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+/// Serialized form of an SMatrix in row-major order: the mask of the occupied rows,
+/// the number of items in each occupied row, the column indices and the values.
+#[derive(Serialize)]
+struct SMatrixDataRef<'a, T> {
+    rows: &'a VectorMask,
+    row_lengths: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<&'a T>,
+    column_index: bool,
+}
+
+#[derive(Deserialize)]
+struct SMatrixData<T> {
+    rows: VectorMask,
+    row_lengths: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<T>,
+    column_index: bool,
+}
+
+impl<T, M, S> Serialize for SMatrix<M, S>
+where
+    T: Serialize,
+    M: MatrixMask,
+    S: Store<Item = T>,
+{
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+    where
+        R: Serializer,
+    {
+        let mut row_lengths = Vec::new();
+        let mut columns = Vec::with_capacity(self.nnz);
+        let mut values = Vec::with_capacity(self.nnz);
+        for r in self.row_mask.iter() {
+            let DataRange(start, end) = self.mask.get_data_range(r);
+            row_lengths.push(end - start);
+            for pos in start..end {
+                columns.push(self.mask.get_column_index(pos.into()));
+                values.push(self.store.get(pos));
+            }
+        }
+
+        SMatrixDataRef {
+            rows: &self.row_mask,
+            row_lengths,
+            columns,
+            values,
+            column_index: self.has_column_index(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T, M, S> Deserialize<'de> for SMatrix<M, S>
+where
+    T: Deserialize<'de>,
+    M: Default + MatrixMask,
+    S: Default + StoreMut<Item = T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = SMatrixData::<T>::deserialize(deserializer)?;
+        if data.rows.number_of_set() != data.row_lengths.len() {
+            return Err(D::Error::custom("row and row length count mismatch"));
+        }
+        let nnz = data
+            .row_lengths
+            .iter()
+            .try_fold(0usize, |nnz, &len| nnz.checked_add(len))
+            .ok_or_else(|| D::Error::custom("row length overflow"))?;
+        if nnz != data.columns.len() || nnz != data.values.len() {
+            return Err(D::Error::custom("column and value count mismatch"));
+        }
+        // the masks may allocate memory for the whole row and column range
+        let row_end = data.rows.iter().last().map_or(0, |r| r + 1);
+        let column_end = match data.columns.iter().max() {
+            Some(&c) => c.checked_add(1).ok_or_else(|| D::Error::custom("column overflow"))?,
+            None => 0,
+        };
+        let end = row_end.max(column_end);
+        if end > max_index_range(nnz) {
+            return Err(D::Error::custom(format!(
+                "index range {} is too sparse for {} items",
+                end, nnz
+            )));
+        }
+
+        let mut mat = SMatrix::new(M::default(), S::default());
+        let mut items = data.columns.into_iter().zip(data.values);
+        for (r, len) in data.rows.iter().zip(data.row_lengths) {
+            if len == 0 {
+                return Err(D::Error::custom(format!("empty row: {}", r)));
+            }
+            for (c, value) in items.by_ref().take(len) {
+                if mat.add(r, c, value).is_some() {
+                    return Err(D::Error::custom(format!("duplicate item: ({},{})", r, c)));
+                }
+            }
+        }
+        mat.row_mask.increase_capacity_to(data.rows.capacity());
+
+        if data.column_index {
+            mat = mat.with_column_index(M::default());
+        }
+        Ok(mat)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::traits::define_update_access;
+
 pub trait Store {
     type Item;
 
@@ -13,3 +15,5 @@ pub trait StoreMut: Store {
 
     fn get_mut(&mut self, idx: usize) -> &mut Self::Item;
 }
+
+define_update_access!();
@@ -1,4 +1,4 @@
-use crate::smat::{Store, StoreMut};
+use crate::smat::{DisjointStoreMut, Store, StoreMut};
 
 pub struct UnitStore {
     unit: (),
@@ -40,3 +40,13 @@ impl StoreMut for UnitStore {
         &mut self.unit
     }
 }
+
+unsafe impl DisjointStoreMut for UnitStore {
+    fn base_ptr(&mut self) -> *mut u8 {
+        &mut self.unit as *mut () as *mut u8
+    }
+
+    unsafe fn get_mut_ptr(_this: *const Self, base: *mut u8, _idx: usize) -> *mut Self::Item {
+        base as *mut ()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::svec::{Store, StoreMut};
+use crate::svec::{DisjointStoreMut, Store, StoreMut};
 use std::mem;
 
 pub struct DenseStore<T> {
@@ -57,3 +57,14 @@ impl<T> StoreMut for DenseStore<T> {
         self.values[idx].as_mut().unwrap()
     }
 }
+
+unsafe impl<T> DisjointStoreMut for DenseStore<T> {
+    fn base_ptr(&mut self) -> *mut u8 {
+        self.values.as_mut_ptr() as *mut u8
+    }
+
+    unsafe fn get_mut_ptr(_this: *const Self, base: *mut u8, idx: usize) -> *mut Self::Item {
+        let value = &mut *(base as *mut Option<T>).add(idx);
+        value.as_mut().unwrap()
+    }
+}
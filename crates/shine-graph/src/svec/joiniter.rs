@@ -1,7 +1,7 @@
 use crate::bits::BitSetViewExt;
 use crate::join::{IntoJoin, Join};
-use crate::svec::{Entry, SVector, Store, StoreMut};
-use crate::traits::{IndexExcl, IndexLowerBound};
+use crate::svec::{DisjointStoreMut, Entry, SVector, Store, StoreMut, UpdateAccess, VectorMask};
+use crate::traits::{IndexExcl, IndexLowerBound, IndexRead, IndexSplit};
 use std::mem;
 
 /// Wrapper to allow immutable access to the elments of an SVector in join and merge oprations.
@@ -43,12 +43,36 @@ where
     }
 }
 
+impl<'a, S> IndexRead for WrapRead<'a, S> where S: Store {}
+
+unsafe impl<'a, S> IndexSplit for WrapRead<'a, S>
+where
+    S: Store,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        WrapRead { vec: self.vec }
+    }
+}
+
 /// Wrapper to allow mutable access to the elments of an SVector in join and merge oprations.
 pub struct WrapUpdate<'a, S>
 where
     S: StoreMut,
 {
-    pub(crate) vec: &'a mut SVector<S>,
+    pub(crate) mask: &'a VectorMask,
+    pub(crate) store: UpdateAccess<'a, S>,
+}
+
+impl<'a, S> WrapUpdate<'a, S>
+where
+    S: StoreMut,
+{
+    pub(crate) fn new(vec: &'a mut SVector<S>) -> Self {
+        WrapUpdate {
+            mask: &vec.mask,
+            store: UpdateAccess::new(&mut vec.store),
+        }
+    }
 }
 
 impl<'a, S> IndexExcl<usize> for WrapUpdate<'a, S>
@@ -58,7 +82,7 @@ where
     type Item = &'a mut S::Item;
 
     fn index(&mut self, idx: usize) -> Self::Item {
-        unsafe { mem::transmute(self.vec.store.get_mut(idx)) } // GAT
+        unsafe { self.store.get_mut(idx) }
     }
 }
 
@@ -67,7 +91,7 @@ where
     S: StoreMut,
 {
     fn lower_bound(&mut self, idx: usize) -> Option<usize> {
-        self.vec.mask.lower_bound(idx)
+        self.mask.lower_bound(idx)
     }
 }
 
@@ -78,7 +102,19 @@ where
     type Store = Self;
 
     fn into_join(self) -> Join<Self::Store> {
-        Join::from_parts(0..self.vec.capacity(), self)
+        Join::from_parts(0..self.mask.capacity(), self)
+    }
+}
+
+unsafe impl<'a, S> IndexSplit for WrapUpdate<'a, S>
+where
+    S: DisjointStoreMut + Sync,
+{
+    unsafe fn split_alias(&mut self) -> Self {
+        WrapUpdate {
+            mask: self.mask,
+            store: self.store.split_alias(),
+        }
     }
 }
 
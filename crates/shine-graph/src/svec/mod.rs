@@ -4,9 +4,13 @@ mod drainiter;
 mod entry;
 mod hashstore;
 mod joiniter;
+mod packedstore;
 mod store;
 mod svector;
+#[cfg(feature = "serialize")]
+mod svectorserde;
 mod svectors;
+mod trackedstore;
 mod unitstore;
 mod vectormask;
 
@@ -16,8 +20,10 @@ pub use self::drainiter::*;
 pub use self::entry::*;
 pub use self::hashstore::*;
 pub use self::joiniter::*;
+pub use self::packedstore::*;
 pub use self::store::*;
 pub use self::svector::*;
 pub use self::svectors::*;
+pub use self::trackedstore::*;
 pub use self::unitstore::*;
 pub use self::vectormask::*;
@@ -0,0 +1,182 @@
+use crate::svec::{DisjointStoreMut, SPVector, Store, StoreMut};
+use std::iter::{Cloned, Zip};
+use std::mem;
+use std::slice;
+
+const NO_SLOT: usize = usize::max_value();
+
+/// Store keeping the values packed in a continuous memory (sparse set).
+/// Removal moves the last value into the freed slot, thus the order of the values is not the order of the indices.
+/// Joins visit the items in index order, thus they access the memory in slot order only after a sort_by_index,
+/// for bulk processing use iter or as_slice.
+/// The index to slot mapping is a dense vector sized to the largest index, thus it has the same per-index
+/// memory cost as a DenseStore of usize, only the values are packed.
+pub struct PackedStore<T> {
+    values: Vec<T>,
+    // index of the item in each slot
+    indices: Vec<usize>,
+    // slot of the item for each index
+    slots: Vec<usize>,
+}
+
+impl<T> PackedStore<T> {
+    pub fn new() -> Self {
+        PackedStore {
+            values: Vec::new(),
+            indices: Vec::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    pub fn new_with_capacity(capacity: usize) -> Self {
+        PackedStore {
+            values: Vec::with_capacity(capacity),
+            indices: Vec::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return the values in slot order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Return the values in slot order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Return the index of the values in slot order.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Iterate over the (index, value) pairs in slot order.
+    pub fn iter(&self) -> Zip<Cloned<slice::Iter<'_, usize>>, slice::Iter<'_, T>> {
+        self.indices.iter().cloned().zip(self.values.iter())
+    }
+
+    /// Iterate over the (index, mutable value) pairs in slot order.
+    pub fn iter_mut(&mut self) -> Zip<Cloned<slice::Iter<'_, usize>>, slice::IterMut<'_, T>> {
+        self.indices.iter().cloned().zip(self.values.iter_mut())
+    }
+
+    /// Reorder the values by index, thus joins access the memory sequentially until the next removal.
+    pub fn sort_by_index(&mut self) {
+        let mut values: Vec<Option<T>> = self.values.drain(..).map(Some).collect();
+        let mut order: Vec<(usize, usize)> = self.indices.drain(..).zip(0..).collect();
+        order.sort_unstable();
+        for (slot, (idx, old_slot)) in order.into_iter().enumerate() {
+            self.values.push(values[old_slot].take().unwrap());
+            self.indices.push(idx);
+            self.slots[idx] = slot;
+        }
+    }
+
+    fn slot(&self, idx: usize) -> usize {
+        let slot = self.slots[idx];
+        assert!(slot != NO_SLOT);
+        slot
+    }
+}
+
+impl<T> Default for PackedStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Store for PackedStore<T> {
+    type Item = T;
+
+    fn get(&self, idx: usize) -> &Self::Item {
+        &self.values[self.slot(idx)]
+    }
+}
+
+impl<T> StoreMut for PackedStore<T> {
+    fn clear(&mut self) {
+        for &idx in &self.indices {
+            self.slots[idx] = NO_SLOT;
+        }
+        self.values.clear();
+        self.indices.clear();
+    }
+
+    fn add(&mut self, idx: usize, value: Self::Item) {
+        if self.slots.len() <= idx {
+            self.slots.resize(idx + 1, NO_SLOT);
+        }
+        assert!(self.slots[idx] == NO_SLOT);
+        self.slots[idx] = self.values.len();
+        self.values.push(value);
+        self.indices.push(idx);
+    }
+
+    fn remove(&mut self, idx: usize) -> Self::Item {
+        let slot = self.slot(idx);
+        self.slots[idx] = NO_SLOT;
+        self.indices.swap_remove(slot);
+        if let Some(&moved) = self.indices.get(slot) {
+            self.slots[moved] = slot;
+        }
+        self.values.swap_remove(slot)
+    }
+
+    fn replace(&mut self, idx: usize, value: Self::Item) -> Self::Item {
+        let slot = self.slot(idx);
+        mem::replace(&mut self.values[slot], value)
+    }
+
+    fn get_mut(&mut self, idx: usize) -> &mut Self::Item {
+        let slot = self.slot(idx);
+        &mut self.values[slot]
+    }
+}
+
+unsafe impl<T> DisjointStoreMut for PackedStore<T> {
+    fn base_ptr(&mut self) -> *mut u8 {
+        self.values.as_mut_ptr() as *mut u8
+    }
+
+    unsafe fn get_mut_ptr(this: *const Self, base: *mut u8, idx: usize) -> *mut Self::Item {
+        // only the (immutable) slot mapping is read, the values are accessed through the base
+        let slot = (*this).slot(idx);
+        (base as *mut T).add(slot)
+    }
+}
+
+impl<T> SPVector<T> {
+    /// Return the values in the storage order for bulk processing.
+    pub fn as_slice(&self) -> &[T] {
+        self.store.as_slice()
+    }
+
+    /// Return the values in the storage order for bulk processing.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.store.as_mut_slice()
+    }
+
+    /// Iterate over the (index, value) pairs in the storage order for bulk processing.
+    pub fn packed_iter(&self) -> Zip<Cloned<slice::Iter<'_, usize>>, slice::Iter<'_, T>> {
+        self.store.iter()
+    }
+
+    /// Iterate over the (index, mutable value) pairs in the storage order for bulk processing.
+    pub fn packed_iter_mut(&mut self) -> Zip<Cloned<slice::Iter<'_, usize>>, slice::IterMut<'_, T>> {
+        self.store.iter_mut()
+    }
+
+    /// Reorder the values by index, see PackedStore::sort_by_index.
+    pub fn sort_by_index(&mut self) {
+        self.store.sort_by_index()
+    }
+}
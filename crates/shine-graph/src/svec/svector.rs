@@ -180,7 +180,7 @@ where
     }
 
     pub fn update(&mut self) -> WrapUpdate<'_, S> {
-        WrapUpdate { vec: self }
+        WrapUpdate::new(self)
     }
 
     pub fn write(&mut self) -> WrapWrite<'_, S> {
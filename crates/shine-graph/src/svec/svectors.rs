@@ -1,4 +1,4 @@
-use crate::svec::{DenseStore, HashStore, SVector, UnitStore, VectorMask};
+use crate::svec::{DenseStore, HashStore, PackedStore, SVector, UnitStore, VectorMask};
 
 pub type SDVector<T> = SVector<DenseStore<T>>;
 pub fn new_dvec<T>() -> SDVector<T> {
@@ -10,6 +10,11 @@ pub fn new_hvec<T>() -> SSVector<T> {
     SVector::new(VectorMask::new(), HashStore::new())
 }
 
+pub type SPVector<T> = SVector<PackedStore<T>>;
+pub fn new_pvec<T>() -> SPVector<T> {
+    SVector::new(VectorMask::new(), PackedStore::new())
+}
+
 pub type STVector = SVector<UnitStore>;
 pub fn new_tvec() -> STVector {
     SVector::new(VectorMask::new(), UnitStore::new())
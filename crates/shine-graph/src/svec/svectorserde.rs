@@ -0,0 +1,75 @@
+use crate::bits::{max_index_range, BitSetData, BitSetViewExt};
+use crate::svec::{SVector, Store, StoreMut, VectorMask};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+/// Serialized form of an SVector: the mask and the values in index order.
+#[derive(Serialize)]
+struct SVectorDataRef<'a, T> {
+    mask: &'a VectorMask,
+    values: Vec<&'a T>,
+}
+
+#[derive(Deserialize)]
+struct SVectorData<T> {
+    mask: BitSetData,
+    values: Vec<T>,
+}
+
+impl<T, S> Serialize for SVector<S>
+where
+    T: Serialize,
+    S: Store<Item = T>,
+{
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+    where
+        R: Serializer,
+    {
+        SVectorDataRef {
+            mask: &self.mask,
+            values: self.data_iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T, S> Deserialize<'de> for SVector<S>
+where
+    T: Deserialize<'de>,
+    S: Default + StoreMut<Item = T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = SVectorData::<T>::deserialize(deserializer)?;
+        let count = data.mask.number_of_set().ok_or_else(|| D::Error::custom("run overflow"))?;
+        if count != data.values.len() {
+            return Err(D::Error::custom(format!(
+                "mask and value count mismatch: {} != {}",
+                count,
+                data.values.len()
+            )));
+        }
+        let end = data.mask.end().ok_or_else(|| D::Error::custom("run overflow"))?;
+        if end > max_index_range(data.values.len()) {
+            return Err(D::Error::custom(format!(
+                "index range {} is too sparse for {} values",
+                end,
+                data.values.len()
+            )));
+        }
+
+        let mask: VectorMask = data.mask.into_bitset()?;
+        let mut store = S::default();
+        for (idx, value) in mask.iter().zip(data.values) {
+            store.add(idx, value);
+        }
+        Ok(SVector {
+            nnz: mask.number_of_set(),
+            mask,
+            store,
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use crate::bits::BitSetViewExt;
+use crate::svec::{new_tvec, STVector, SVector, Store, StoreMut, UnitStore, VectorMask, WrapRead};
+use std::mem;
+
+/// The changes of a tracked vector since the last drain.
+pub struct Changes {
+    added: STVector,
+    removed: STVector,
+    mutated: STVector,
+}
+
+impl Changes {
+    pub fn new() -> Self {
+        Changes {
+            added: new_tvec(),
+            removed: new_tvec(),
+            mutated: new_tvec(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_zero() && self.removed.is_zero() && self.mutated.is_zero()
+    }
+
+    /// Indices of the items that were added.
+    pub fn added(&self) -> WrapRead<'_, UnitStore> {
+        self.added.read()
+    }
+
+    /// Indices of the items that were removed.
+    pub fn removed(&self) -> WrapRead<'_, UnitStore> {
+        self.removed.read()
+    }
+
+    /// Indices of the items that were neither added nor removed, but mutable access was granted to them.
+    pub fn changed(&self) -> WrapRead<'_, UnitStore> {
+        self.mutated.read()
+    }
+
+    fn add(&mut self, idx: usize) {
+        if self.removed.remove(idx).is_some() {
+            self.mutated.add(idx, ());
+        } else {
+            self.added.add(idx, ());
+        }
+    }
+
+    fn remove(&mut self, idx: usize) {
+        if self.added.remove(idx).is_none() {
+            self.mutated.remove(idx);
+            self.removed.add(idx, ());
+        }
+    }
+
+    fn mutate(&mut self, idx: usize) {
+        if !self.added.contains(idx) {
+            self.mutated.add(idx, ());
+        }
+    }
+}
+
+impl Default for Changes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Store adaptor recording the added, removed and mutated items.
+/// Any mutable access (get_mut, update, write, entries) is considered as a mutation.
+/// As get_mut modifies the shared change masks, it is not a DisjointStoreMut and update joins cannot run in parallel.
+pub struct TrackedStore<S>
+where
+    S: StoreMut,
+{
+    store: S,
+    // the store has no access to the mask of the vector, thus the stored indices are
+    // also tracked here to record the removals on clear
+    items: VectorMask,
+    changes: Changes,
+}
+
+impl<S> TrackedStore<S>
+where
+    S: StoreMut,
+{
+    pub fn new(store: S) -> Self {
+        TrackedStore {
+            store,
+            items: VectorMask::new(),
+            changes: Changes::new(),
+        }
+    }
+
+    pub fn changes(&self) -> &Changes {
+        &self.changes
+    }
+
+    pub fn drain_changes(&mut self) -> Changes {
+        mem::replace(&mut self.changes, Changes::new())
+    }
+}
+
+impl<S> Default for TrackedStore<S>
+where
+    S: Default + StoreMut,
+{
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S> Store for TrackedStore<S>
+where
+    S: StoreMut,
+{
+    type Item = S::Item;
+
+    fn get(&self, idx: usize) -> &Self::Item {
+        self.store.get(idx)
+    }
+}
+
+impl<S> StoreMut for TrackedStore<S>
+where
+    S: StoreMut,
+{
+    fn clear(&mut self) {
+        for idx in self.items.iter() {
+            self.changes.remove(idx);
+        }
+        self.items.clear();
+        self.store.clear();
+    }
+
+    fn add(&mut self, idx: usize, value: Self::Item) {
+        self.items.add(idx);
+        self.changes.add(idx);
+        self.store.add(idx, value);
+    }
+
+    fn replace(&mut self, idx: usize, value: Self::Item) -> Self::Item {
+        self.changes.mutate(idx);
+        self.store.replace(idx, value)
+    }
+
+    fn remove(&mut self, idx: usize) -> Self::Item {
+        self.items.remove(idx);
+        self.changes.remove(idx);
+        self.store.remove(idx)
+    }
+
+    fn get_mut(&mut self, idx: usize) -> &mut Self::Item {
+        self.changes.mutate(idx);
+        self.store.get_mut(idx)
+    }
+}
+
+/// Sparse vector recording the changes of its items.
+pub type TrackedSVector<S> = SVector<TrackedStore<S>>;
+
+impl<S> SVector<S>
+where
+    S: StoreMut,
+{
+    /// Start tracking the changes, the current items are not reported as added.
+    pub fn into_tracked(self) -> TrackedSVector<S> {
+        let mut store = TrackedStore::new(self.store);
+        store.items.union_with(&self.mask);
+        SVector {
+            nnz: self.nnz,
+            mask: self.mask,
+            store,
+        }
+    }
+}
+
+impl<S> TrackedSVector<S>
+where
+    S: StoreMut,
+{
+    pub fn changes(&self) -> &Changes {
+        self.store.changes()
+    }
+
+    /// Return the changes since the last drain and restart the tracking.
+    pub fn drain_changes(&mut self) -> Changes {
+        self.store.drain_changes()
+    }
+
+    pub fn added(&self) -> WrapRead<'_, UnitStore> {
+        self.store.changes().added()
+    }
+
+    pub fn removed(&self) -> WrapRead<'_, UnitStore> {
+        self.store.changes().removed()
+    }
+
+    pub fn changed(&self) -> WrapRead<'_, UnitStore> {
+        self.store.changes().changed()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::svec::{Store, StoreMut};
+use crate::svec::{DisjointStoreMut, Store, StoreMut};
 
 pub struct UnitStore {
     unit: (),
@@ -41,3 +41,13 @@ impl StoreMut for UnitStore {
         &mut self.unit
     }
 }
+
+unsafe impl DisjointStoreMut for UnitStore {
+    fn base_ptr(&mut self) -> *mut u8 {
+        &mut self.unit as *mut () as *mut u8
+    }
+
+    unsafe fn get_mut_ptr(_this: *const Self, base: *mut u8, _idx: usize) -> *mut Self::Item {
+        base as *mut ()
+    }
+}
@@ -16,3 +16,120 @@ pub trait IndexLowerBound<I>: IndexExcl<I> {
 
 use shine_graph_macro::impl_indexlowerbound_for_indexlowerbound_tuple;
 impl_indexlowerbound_for_indexlowerbound_tuple! {2,3,4,5,6,7,8,9,10}
+
+/// Marker for the read-only stores. The items are shared references, thus
+/// they don't borrow the store exclusively and can be kept after the next index operation.
+pub trait IndexRead: IndexLowerBound<usize> {}
+
+use shine_graph_macro::impl_indexread_for_indexread_tuple;
+impl_indexread_for_indexread_tuple! {2,3,4,5,6,7,8,9,10}
+
+/// Used to process disjoint index ranges of a join in parallel.
+///
+/// # Safety
+/// Implementors must guarantee that items of different indices never alias and
+/// lower_bound does not modify any shared state.
+pub unsafe trait IndexSplit: IndexLowerBound<usize> {
+    /// Create an alias accessing the same container.
+    ///
+    /// # Safety
+    /// The caller has to ensure that the original and the aliases never index the same item.
+    unsafe fn split_alias(&mut self) -> Self;
+}
+
+use shine_graph_macro::impl_indexsplit_for_indexsplit_tuple;
+impl_indexsplit_for_indexsplit_tuple! {2,3,4,5,6,7,8,9,10}
+
+/// Define the DisjointStoreMut trait and the UpdateAccess used by the update wrappers for the StoreMut
+/// of the calling module. The containers have their own Store traits, but the aliasing rules of the
+/// mutable item access are shared and they are kept in this single place.
+macro_rules! define_update_access {
+    () => {
+        /// Stores where the items of different indices are disjoint in memory, thus they can be accessed concurrently
+        /// through raw pointers by the aliases of a split join (see IndexSplit).
+        ///
+        /// # Safety
+        /// The pointer returned by get_mut_ptr must be derived from the base and must not alias the item of any other index.
+        /// get_mut_ptr must not create a mutable reference to the store or to any data shared by the indices.
+        pub unsafe trait DisjointStoreMut: StoreMut {
+            /// Return the base pointer of the items. It is taken once, before the store is shared by the aliases.
+            fn base_ptr(&mut self) -> *mut u8;
+
+            /// Return a pointer to the item of the index.
+            ///
+            /// # Safety
+            /// The base must be taken from this store and the store must not be modified since then.
+            unsafe fn get_mut_ptr(this: *const Self, base: *mut u8, idx: usize) -> *mut Self::Item;
+        }
+
+        type GetMutPtr<S> = unsafe fn(*const S, *mut u8, usize) -> *mut <S as Store>::Item;
+
+        /// Mutable access of the items used by the update wrappers. The store is borrowed only for the
+        /// duration of a get_mut until the access is split, the aliases use the raw access of DisjointStoreMut.
+        pub(crate) struct UpdateAccess<'a, S>
+        where
+            S: StoreMut,
+        {
+            store: *mut S,
+            raw: Option<(*mut u8, GetMutPtr<S>)>,
+            phantom: ::std::marker::PhantomData<&'a mut S>,
+        }
+
+        impl<'a, S> UpdateAccess<'a, S>
+        where
+            S: StoreMut,
+        {
+            pub(crate) fn new(store: &'a mut S) -> Self {
+                UpdateAccess {
+                    store,
+                    raw: None,
+                    phantom: ::std::marker::PhantomData,
+                }
+            }
+
+            /// Create a copy accessing the same store.
+            ///
+            /// # Safety
+            /// The caller has to ensure that the original and the copy are not used at the same time
+            /// unless the access is already split.
+            pub(crate) unsafe fn alias(&self) -> Self {
+                UpdateAccess {
+                    store: self.store,
+                    raw: self.raw,
+                    phantom: ::std::marker::PhantomData,
+                }
+            }
+
+            /// # Safety
+            /// The caller has to ensure that the item of an index is not accessed multiple times.
+            pub(crate) unsafe fn get_mut(&mut self, idx: usize) -> &'a mut S::Item {
+                match self.raw {
+                    Some((base, get_mut_ptr)) => &mut *get_mut_ptr(self.store, base, idx),
+                    None => &mut *((*self.store).get_mut(idx) as *mut S::Item),
+                }
+            }
+        }
+
+        impl<'a, S> UpdateAccess<'a, S>
+        where
+            S: DisjointStoreMut,
+        {
+            /// Create an alias for concurrent access. The first split takes the base pointer from the store
+            /// while the access is still exclusive, afterwards the store is never borrowed.
+            ///
+            /// # Safety
+            /// The caller has to ensure that the original and the aliases never access the same item.
+            pub(crate) unsafe fn split_alias(&mut self) -> Self {
+                if self.raw.is_none() {
+                    self.raw = Some(((*self.store).base_ptr(), S::get_mut_ptr));
+                }
+                self.alias()
+            }
+        }
+
+        unsafe impl<'a, S> Send for UpdateAccess<'a, S> where S: StoreMut + Send {}
+
+        unsafe impl<'a, S> Sync for UpdateAccess<'a, S> where S: StoreMut + Sync {}
+    };
+}
+pub(crate) use define_update_access;
@@ -0,0 +1,108 @@
+use log::debug;
+use shine_graph::algo::*;
+use shine_graph::smat::{new_dmat, new_hdmat, new_tmat, STMatrix};
+use shine_testutils::init_test;
+
+fn graph_from(edges: &[(usize, usize)]) -> STMatrix {
+    let mut g = new_tmat();
+    for &(a, b) in edges {
+        g.add(a, b, ());
+    }
+    g
+}
+
+#[test]
+fn test_traversal() {
+    init_test(module_path!());
+
+    let g = graph_from(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 0)]);
+
+    debug!("bfs");
+    let nodes: Vec<_> = bfs(&g, 0).collect();
+    assert_eq!(nodes, vec![0, 1, 2, 3, 4]);
+    let mut it = bfs(&g, 0);
+    let depths: Vec<_> = (0..5).map(|_| it.next_with_depth().unwrap()).collect();
+    assert_eq!(depths, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+    assert_eq!(bfs(&g, 4).collect::<Vec<_>>(), vec![4]);
+    assert_eq!(bfs(&g, 100).collect::<Vec<_>>(), vec![100]);
+
+    debug!("dfs");
+    let nodes: Vec<_> = dfs(&g, 0).collect();
+    assert_eq!(nodes, vec![0, 1, 3, 4, 2]);
+    let nodes: Vec<_> = dfs(&g, 5).collect();
+    assert_eq!(nodes, vec![5, 0, 1, 3, 4, 2]);
+}
+
+#[test]
+fn test_toposort() {
+    init_test(module_path!());
+
+    let g = graph_from(&[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+    let order = toposort(&g).unwrap();
+    assert_eq!(order.len(), 6);
+    let pos = |n: usize| order.iter().position(|&x| x == n).unwrap();
+    for &(a, b) in &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)] {
+        assert!(pos(a) < pos(b));
+    }
+
+    let g = graph_from(&[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+    assert_eq!(toposort(&g), Err(Cycle(vec![1, 2, 3])));
+
+    let g = graph_from(&[(7, 7)]);
+    assert_eq!(toposort(&g), Err(Cycle(vec![7])));
+}
+
+#[test]
+fn test_scc() {
+    init_test(module_path!());
+
+    let g = graph_from(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)]);
+    let components = strongly_connected_components(&g);
+    assert_eq!(components, vec![vec![3, 4, 5], vec![0, 1, 2], vec![6, 7]]);
+
+    let g = graph_from(&[(0, 1), (1, 2)]);
+    assert_eq!(strongly_connected_components(&g), vec![vec![2], vec![1], vec![0]]);
+}
+
+#[test]
+fn test_connected_components() {
+    init_test(module_path!());
+
+    let g = graph_from(&[(3, 1), (1, 4), (10, 12), (7, 12), (2, 2), (1_000_000, 4)]);
+    let components = connected_components(&g);
+    assert_eq!(components, vec![vec![1, 3, 4, 1_000_000], vec![2], vec![7, 10, 12]]);
+}
+
+#[test]
+fn test_dijkstra() {
+    init_test(module_path!());
+
+    let mut g = new_dmat::<u32>();
+    g.add(0, 1, 7);
+    g.add(0, 2, 9);
+    g.add(0, 5, 14);
+    g.add(1, 2, 10);
+    g.add(1, 3, 15);
+    g.add(2, 3, 11);
+    g.add(2, 5, 2);
+    g.add(3, 4, 6);
+    g.add(5, 4, 9);
+
+    let paths = dijkstra(&g, 0);
+    assert_eq!(paths.source(), 0);
+    assert_eq!(paths.distance(4), Some(&20));
+    assert_eq!(paths.path_to(4), Some(vec![0, 2, 5, 4]));
+    assert_eq!(paths.distance(3), Some(&20));
+    assert_eq!(paths.path_to(3), Some(vec![0, 2, 3]));
+    assert_eq!(paths.path_to(0), Some(vec![0]));
+    assert_eq!(paths.distance(6), None);
+    assert_eq!(paths.path_to(6), None);
+
+    let mut g = new_hdmat::<(f32, &str)>();
+    g.add(10, 20, (0.5, "a"));
+    g.add(20, 30, (0.25, "b"));
+    g.add(10, 30, (1.0, "c"));
+    let paths = dijkstra_by(&g, 10, |e| e.0);
+    assert_eq!(paths.distance(30), Some(&0.75));
+    assert_eq!(paths.path_to(30), Some(vec![10, 20, 30]));
+}
@@ -3,7 +3,7 @@ use rand;
 use log::{debug, trace};
 use permutohedron::Heap;
 use rand::Rng;
-use shine_graph::bits::{bitops, BitBlock, BitSet, BitSetView, BitSetViewExt};
+use shine_graph::bits::{bitops, blockops, BitBlock, BitSet, BitSetView, BitSetViewExt};
 use shine_testutils::init_test;
 use std::collections::HashSet;
 
@@ -413,3 +413,213 @@ fn test_ops_random() {
     debug!("ops random - u128");
     test_ops_random_::<u128>(range, count);
 }
+
+fn test_set_algebra_<B: BitBlock>(range: usize, count: usize) {
+    let mut rng = rand::thread_rng();
+
+    let mut bitset1 = BitSet::<B>::new();
+    let mut set1 = HashSet::<usize>::new();
+    let mut bitset2 = BitSet::<B>::new();
+    let mut set2 = HashSet::<usize>::new();
+    for _ in 0..count {
+        let b1 = rng.gen_range(0, range);
+        bitset1.add(b1);
+        set1.insert(b1);
+
+        let b2 = rng.gen_range(0, range / 2);
+        bitset2.add(b2);
+        set2.insert(b2);
+    }
+
+    let check = |bitset: &BitSet<B>, expected: HashSet<usize>| {
+        let mut expected: Vec<usize> = expected.into_iter().collect();
+        expected.sort();
+        assert_eq!(bitset.number_of_set(), expected.len());
+        check_bitset(bitset, &expected);
+    };
+
+    debug!("xor view");
+    {
+        let mut expected: Vec<usize> = set1.symmetric_difference(&set2).cloned().collect();
+        expected.sort();
+        check_bitset(&bitops::xor2(&bitset1, &bitset2), &expected);
+        check_bitset(&bitops::xor2(&bitset2, &bitset1), &expected);
+    }
+
+    debug!("union");
+    {
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset1);
+        check(&bitset, set1.clone());
+        bitset.union_with(&bitset2);
+        check(&bitset, set1.union(&set2).cloned().collect());
+    }
+
+    debug!("intersection");
+    {
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset1);
+        bitset.intersect_with(&bitset2);
+        check(&bitset, set1.intersection(&set2).cloned().collect());
+        bitset.intersect_with(&BitSet::new());
+        check(&bitset, HashSet::new());
+    }
+
+    debug!("difference");
+    {
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset1);
+        bitset.difference_with(&bitset2);
+        check(&bitset, set1.difference(&set2).cloned().collect());
+
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset2);
+        bitset.difference_with(&bitset1);
+        check(&bitset, set2.difference(&set1).cloned().collect());
+    }
+
+    debug!("operations with a shorter set");
+    {
+        let far = range * 16;
+        let mut bitset = BitSet::<B>::new();
+        bitset.add(far);
+        bitset.union_with(&bitset2);
+        let mut expected = set2.clone();
+        expected.insert(far);
+        check(&bitset, expected);
+        bitset.difference_with(&bitset2);
+        check(&bitset, [far].iter().cloned().collect());
+    }
+
+    debug!("symmetric difference");
+    {
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset2);
+        bitset.symmetric_difference_with(&bitset1);
+        check(&bitset, set1.symmetric_difference(&set2).cloned().collect());
+        bitset.symmetric_difference_with(&bitset1);
+        check(&bitset, set2.clone());
+    }
+
+    debug!("ranges");
+    {
+        let mut bitset = BitSet::<B>::new();
+        let mut expected = set1.clone();
+        bitset.union_with(&bitset1);
+        for _ in 0..10 {
+            let a = rng.gen_range(0, range);
+            let b = rng.gen_range(a, range + 1);
+            assert_eq!(
+                bitset.count_range(a..b),
+                expected.iter().filter(|&&x| x >= a && x < b).count()
+            );
+            if rng.gen() {
+                bitset.add_range(a..b);
+                expected.extend(a..b);
+            } else {
+                bitset.remove_range(a..b);
+                expected.retain(|&x| x < a || x >= b);
+            }
+            check(&bitset, expected.clone());
+        }
+    }
+
+    debug!("navigation");
+    {
+        let mut expected: Vec<usize> = set1.iter().cloned().collect();
+        expected.sort();
+        assert_eq!(bitset1.first(), expected.first().cloned());
+        assert_eq!(bitset1.last(), expected.last().cloned());
+        for _ in 0..count {
+            let pos = rng.gen_range(0, range + 10);
+            assert_eq!(bitset1.next_after(pos), expected.iter().cloned().find(|&x| x > pos));
+            assert_eq!(bitset1.prev_before(pos), expected.iter().cloned().rev().find(|&x| x < pos));
+        }
+        for &pos in &expected {
+            assert_eq!(bitset1.prev_before(pos + 1), Some(pos));
+            if pos > 0 {
+                assert_eq!(bitset1.next_after(pos - 1), Some(pos));
+            }
+        }
+    }
+
+    debug!("shrink");
+    {
+        let mut bitset = BitSet::<B>::new();
+        bitset.union_with(&bitset1);
+        bitset.remove_range(range / 4..range);
+        bitset.shrink_to_fit();
+        assert!(bitset.capacity() <= range / 4 + B::bit_count());
+        check(&bitset, set1.iter().cloned().filter(|&x| x < range / 4).collect());
+
+        bitset.clear();
+        bitset.shrink_to_fit();
+        assert_eq!(bitset.first(), None);
+        assert_eq!(bitset.last(), None);
+        bitset.add(range);
+        check(&bitset, [range].iter().cloned().collect());
+    }
+}
+
+#[test]
+fn test_set_algebra() {
+    init_test(module_path!());
+
+    let count = 256;
+    let range = 4096;
+
+    debug!("set algebra - u8");
+    test_set_algebra_::<u8>(range, count);
+    debug!("set algebra - u16");
+    test_set_algebra_::<u16>(range, count);
+    debug!("set algebra - u32");
+    test_set_algebra_::<u32>(range, count);
+    debug!("set algebra - u64");
+    test_set_algebra_::<u64>(range, count);
+    debug!("set algebra - u128");
+    test_set_algebra_::<u128>(range, count);
+
+    let count = 4096;
+    debug!("dense set algebra - u8");
+    test_set_algebra_::<u8>(range, count);
+    debug!("dense set algebra - u64");
+    test_set_algebra_::<u64>(range, count);
+}
+
+fn test_blockops_<B: BitBlock>(len: usize)
+where
+    rand::distributions::Standard: rand::distributions::Distribution<B>,
+{
+    let mut rng = rand::thread_rng();
+    let dst: Vec<B> = (0..len).map(|_| rng.gen()).collect();
+    let src: Vec<B> = (0..rng.gen_range(0, len + 2)).map(|_| rng.gen()).collect();
+    trace!("dst: {}, src: {}", dst.len(), src.len());
+
+    assert_eq!(blockops::popcount(&dst), blockops::scalar::popcount(&dst));
+
+    let ops: [(fn(&mut [B], &[B]), fn(&mut [B], &[B])); 3] = [
+        (blockops::and_assign, blockops::scalar::and_assign),
+        (blockops::or_assign, blockops::scalar::or_assign),
+        (blockops::andnot_assign, blockops::scalar::andnot_assign),
+    ];
+    for (op, scalar_op) in ops.iter() {
+        let mut result = dst.clone();
+        op(&mut result, &src);
+        let mut expected = dst.clone();
+        scalar_op(&mut expected, &src);
+        assert!(result == expected);
+    }
+}
+
+#[test]
+fn test_blockops() {
+    init_test(module_path!());
+
+    for len in 0..100 {
+        test_blockops_::<u8>(len);
+        test_blockops_::<u16>(len);
+        test_blockops_::<u32>(len);
+        test_blockops_::<u64>(len);
+        test_blockops_::<u128>(len);
+    }
+}
@@ -0,0 +1,141 @@
+use log::debug;
+use shine_graph::linalg::*;
+use shine_graph::smat::{new_amat, new_dmat, new_hdmat, SDMatrix};
+use shine_graph::svec::{new_dvec, new_hvec, SDVector};
+use shine_testutils::init_test;
+
+fn vec_from(items: &[(usize, i32)]) -> SDVector<i32> {
+    let mut v = new_dvec();
+    for &(i, x) in items {
+        v.add(i, x);
+    }
+    v
+}
+
+fn mat_from(items: &[(usize, usize, i32)]) -> SDMatrix<i32> {
+    let mut m = new_dmat();
+    for &(r, c, x) in items {
+        m.add(r, c, x);
+    }
+    m
+}
+
+fn vec_items(v: &SDVector<i32>) -> Vec<(usize, i32)> {
+    v.mask_iter().map(|i| (i, *v.get(i).unwrap())).collect()
+}
+
+fn mat_items(m: &SDMatrix<i32>, size: usize) -> Vec<(usize, usize, i32)> {
+    let mut items = Vec::new();
+    for r in 0..size {
+        for c in 0..size {
+            if let Some(x) = m.get(r, c) {
+                items.push((r, c, *x));
+            }
+        }
+    }
+    items
+}
+
+#[test]
+fn test_vector_ops() {
+    init_test(module_path!());
+
+    let a = vec_from(&[(1, 1), (3, 3), (7, 7)]);
+    let b = vec_from(&[(0, 10), (3, 30), (8, 80)]);
+
+    debug!("add");
+    let mut y = new_dvec();
+    vec_add(&a, &b, &mut y);
+    assert_eq!(vec_items(&y), vec![(0, 10), (1, 1), (3, 33), (7, 7), (8, 80)]);
+
+    debug!("elementwise mul");
+    vec_elem_mul(&a, &b, &mut y);
+    assert_eq!(vec_items(&y), vec![(3, 90)]);
+    assert_eq!(vec_dot(&a, &b), 90);
+
+    debug!("map, scale");
+    let mut z = new_hvec();
+    vec_map(&a, |x| *x as f32 / 2., &mut z);
+    assert_eq!(z.get(3), Some(&1.5));
+    assert_eq!(z.nnz(), 3);
+    let mut y = vec_from(&[(1, 1), (3, 3)]);
+    vec_scale(&mut y, -2);
+    assert_eq!(vec_items(&y), vec![(1, -2), (3, -6)]);
+}
+
+#[test]
+fn test_matrix_vector_ops() {
+    init_test(module_path!());
+
+    // 0 -> 1, 0 -> 2, 1 -> 2, 2 -> 0, 3 -> 2
+    let a = mat_from(&[(0, 1, 1), (0, 2, 2), (1, 2, 3), (2, 0, 4), (3, 2, 5)]);
+    let x = vec_from(&[(0, 1), (2, 10)]);
+
+    debug!("matrix-vector");
+    let mut y = new_dvec();
+    mat_vec_mul(&a, &x, &mut y);
+    assert_eq!(vec_items(&y), vec![(0, 20), (1, 30), (2, 4), (3, 50)]);
+
+    debug!("vector-matrix");
+    vec_mat_mul(&x, &a, &mut y);
+    assert_eq!(vec_items(&y), vec![(0, 40), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn test_matrix_ops() {
+    init_test(module_path!());
+
+    let a = mat_from(&[(0, 1, 1), (0, 2, 2), (1, 2, 3), (2, 0, 4)]);
+    let b = mat_from(&[(0, 0, 1), (1, 0, 2), (2, 1, 3), (2, 2, 4)]);
+
+    debug!("matrix-matrix");
+    let mut c = new_dmat();
+    mat_mat_mul(&a, &b, &mut c);
+    assert_eq!(
+        mat_items(&c, 4),
+        vec![(0, 0, 2), (0, 1, 6), (0, 2, 8), (1, 1, 9), (1, 2, 12), (2, 0, 4)]
+    );
+
+    debug!("adjacency power into a different mask");
+    let mut c2 = new_hdmat();
+    mat_mat_mul(&a, &a, &mut c2);
+    assert_eq!(c2.get(0, 2), Some(&3));
+    assert_eq!(c2.get(0, 0), Some(&8));
+    assert_eq!(c2.nnz(), 5);
+
+    debug!("transpose");
+    let mut t = new_amat();
+    transpose(&a, &mut t);
+    assert_eq!(t.nnz(), 4);
+    assert_eq!(t.get(1, 0), Some(&1));
+    assert_eq!(t.get(2, 1), Some(&3));
+    assert_eq!(t.get(0, 2), Some(&4));
+
+    debug!("add");
+    mat_add(&a, &b, &mut c);
+    assert_eq!(
+        mat_items(&c, 4),
+        vec![
+            (0, 0, 1),
+            (0, 1, 1),
+            (0, 2, 2),
+            (1, 0, 2),
+            (1, 2, 3),
+            (2, 0, 4),
+            (2, 1, 3),
+            (2, 2, 4)
+        ]
+    );
+
+    debug!("elementwise mul");
+    let b = mat_from(&[(0, 2, 10), (1, 1, 10), (2, 0, 10)]);
+    mat_elem_mul(&a, &b, &mut c);
+    assert_eq!(mat_items(&c, 4), vec![(0, 2, 20), (2, 0, 40)]);
+
+    debug!("map, scale");
+    let mut f = new_dmat();
+    mat_map(&a, |x| f64::from(*x) * 0.5, &mut f);
+    assert_eq!(f.get(1, 2), Some(&1.5));
+    mat_scale(&mut c, 2);
+    assert_eq!(mat_items(&c, 4), vec![(0, 2, 40), (2, 0, 80)]);
+}
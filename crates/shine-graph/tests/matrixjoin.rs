@@ -1,7 +1,7 @@
 use log::debug;
 
 use shine_graph::join::IntoJoinExt;
-use shine_graph::smat::new_amat;
+use shine_graph::smat::{new_amat, new_dmat, new_hdmat, CSMatrixMask, HCSMatrixMask, MatrixMask, SMatrix, StoreMut};
 use shine_graph::svec::new_dvec;
 use shine_testutils::init_test;
 
@@ -170,3 +170,134 @@ fn test_vec_mat_join() {
          , (, (3,0, Some(6) -> None), (3,1, Some(6) -> None), (3,2, Some(6) -> None), (3,3, Some(6) -> None), (3,4, Some(6) -> Some(36)), (3,5, Some(6) -> Some(37)))"
     );
 }
+
+fn test_column_join_<M: MatrixMask, S: StoreMut<Item = usize>>(mut m1: SMatrix<M, S>, column_mask: M) {
+    let mut v1 = new_dvec::<usize>();
+    v1.add(3, 3);
+    v1.add(7, 7);
+    v1.add(14, 14);
+
+    m1.add(3, 4, 34);
+    m1.add(3, 7, 37);
+    m1.add(14, 7, 147);
+    // index is built from the existing items
+    let mut m1 = m1.with_column_index(column_mask);
+    assert!(m1.has_column_index());
+    m1.add(23, 3, 233);
+    m1.add(23, 7, 237);
+    m1.add(1, 7, 17);
+    m1.add(5, 5, 55);
+    assert_eq!(m1.remove(5, 5), Some(55));
+
+    debug!("empty column");
+    let mut s = String::new();
+    m1.read_column(1).join_all(|id, e| s = format!("{}, {}={}", s, id, e));
+    assert_eq!(s, "");
+    m1.read_column(5).join_all(|id, e| s = format!("{}, {}={}", s, id, e));
+    assert_eq!(s, "");
+    m1.read_column(1000).join_all(|id, e| s = format!("{}, {}={}", s, id, e));
+    assert_eq!(s, "");
+
+    debug!("column read");
+    let mut s = String::new();
+    m1.read_column(7).join_all(|id, e| s = format!("{}, {}={}", s, id, e));
+    assert_eq!(s, ", 1=17, 3=37, 14=147, 23=237");
+
+    debug!("column update");
+    let mut s = String::new();
+    m1.update_column(7).join_all(|id, e| {
+        *e += 1;
+        s = format!("{}, {}={}", s, id, e);
+    });
+    assert_eq!(s, ", 1=18, 3=38, 14=148, 23=238");
+    assert_eq!(m1.get(14, 7), Some(&148));
+
+    debug!("vec read, column read");
+    let mut s = String::new();
+    (v1.read(), m1.read_columns()).join_all(|id1, (v, c)| {
+        let mut s2 = String::new();
+        c.join_all(|id2, e| {
+            s2 = format!("{}, ({},{}, {} -> {:?})", s2, id2, id1, v, e);
+        });
+        s = format!("{}, ({})", s, s2);
+    });
+    assert_eq!(
+        s,
+        ", (, (23,3, 3 -> 233)), (, (1,7, 7 -> 18), (3,7, 7 -> 38), (14,7, 7 -> 148), (23,7, 7 -> 238))"
+    );
+
+    debug!("column update, column join");
+    let mut s = String::new();
+    m1.update_columns().join_all(|id1, c| {
+        c.join_all(|id2, e| {
+            *e += 1;
+            s = format!("{}, ({},{})={}", s, id2, id1, e);
+        });
+    });
+    assert_eq!(s, ", (23,3)=234, (3,4)=35, (1,7)=19, (3,7)=39, (14,7)=149, (23,7)=239");
+
+    m1.clear();
+    let mut s = String::new();
+    m1.read_column(7).join_all(|id, e| s = format!("{}, {}={}", s, id, e));
+    assert_eq!(s, "");
+}
+
+#[test]
+fn test_column_join() {
+    init_test(module_path!());
+
+    debug!("SparseDMatrix/CSMatrix");
+    test_column_join_(new_dmat::<usize>(), CSMatrixMask::new());
+    debug!("SparseAMatrix/CSMatrix");
+    test_column_join_(new_amat::<usize>(), CSMatrixMask::new());
+    debug!("SparseHDMatrix/HCSMatrix");
+    test_column_join_(new_hdmat::<usize>(), HCSMatrixMask::new());
+}
+
+fn test_long_row_join_<M: MatrixMask, S: StoreMut<Item = usize>>(mut m1: SMatrix<M, S>, column_mask: M) {
+    let mut v1 = new_dvec::<usize>();
+    for i in 0..10000 {
+        if i % 5 == 0 {
+            v1.add(i, i);
+        }
+        if i % 3 == 0 {
+            m1.add(7, i, 7 * i);
+            m1.add(i, 7, 7 * i);
+        }
+    }
+    let mut m1 = m1.with_column_index(column_mask);
+
+    // the expected result is collected by the random access (bisection) of the items
+    let expected: Vec<_> = (0..10000).filter_map(|i| Some((i, *v1.get(i)?, *m1.get(7, i)?))).collect();
+    assert_eq!(expected.len(), 667);
+
+    debug!("vec read, long row read");
+    let mut items = Vec::new();
+    (v1.read(), m1.read_row(7)).join_all(|id, (v, e)| items.push((id, *v, *e)));
+    assert_eq!(items, expected);
+
+    debug!("vec read, long row update");
+    let mut items = Vec::new();
+    (v1.read(), m1.update_row(7)).join_all(|id, (v, e)| items.push((id, *v, *e)));
+    assert_eq!(items, expected);
+
+    debug!("vec read, long column read");
+    let mut items = Vec::new();
+    (v1.read(), m1.read_column(7)).join_all(|id, (v, e)| items.push((id, *v, *e)));
+    assert_eq!(items, expected);
+
+    debug!("vec read, long column update");
+    let mut items = Vec::new();
+    (v1.read(), m1.update_column(7)).join_all(|id, (v, e)| items.push((id, *v, *e)));
+    assert_eq!(items, expected);
+}
+
+#[test]
+fn test_long_row_join() {
+    init_test(module_path!());
+
+    debug!("SparseDMatrix/CSMatrix");
+    test_long_row_join_(new_dmat::<usize>(), CSMatrixMask::new());
+    debug!("SparseHDMatrix/HCSMatrix");
+    test_long_row_join_(new_hdmat::<usize>(), HCSMatrixMask::new());
+}
@@ -0,0 +1,152 @@
+#![cfg(feature = "parallel")]
+
+use log::debug;
+use rand::Rng;
+use shine_graph::join::{IntoJoin, IntoJoinExt};
+use shine_graph::parjoin::IntoParJoinExt;
+use shine_graph::smat::new_dmat;
+use shine_graph::svec::{new_dvec, new_pvec, DenseStore, TrackedStore, WrapUpdate};
+use shine_graph::traits::IndexSplit;
+use shine_testutils::init_test;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[test]
+fn test_par_vector_join() {
+    init_test(module_path!());
+
+    let mut rng = rand::thread_rng();
+    let mut v1 = new_dvec::<usize>();
+    let mut v2 = new_dvec::<usize>();
+    for _ in 0..20000 {
+        let i = rng.gen_range(0, 100_000);
+        v1.add(i, i + 1);
+        let i = rng.gen_range(0, 100_000);
+        v2.add(i, 0);
+    }
+
+    let mut expected = Vec::new();
+    (v1.read(), v2.read()).join_all(|id, _| expected.push(id));
+    debug!("common items: {}", expected.len());
+
+    for &chunk in &[1, 32, 1000, 1 << 20] {
+        debug!("chunk size: {}", chunk);
+        let count = AtomicUsize::new(0);
+        (v1.read(), v2.update())
+            .into_join()
+            .par_for_each_chunked(chunk, |id, (a, b)| {
+                assert_eq!(id + 1, *a);
+                *b += *a;
+                count.fetch_add(1, Ordering::Relaxed);
+            });
+        assert_eq!(count.load(Ordering::Relaxed), expected.len());
+    }
+
+    let visited = Mutex::new(Vec::new());
+    v2.read().par_join_all(|id, b| {
+        if *b > 0 {
+            assert_eq!(*b, 4 * (id + 1));
+            visited.lock().unwrap().push(id);
+        }
+    });
+    let mut visited = visited.into_inner().unwrap();
+    visited.sort();
+    assert_eq!(visited, expected);
+}
+
+#[test]
+fn test_par_packed_join() {
+    init_test(module_path!());
+
+    let mut v1 = new_pvec::<usize>();
+    for i in (0..50000).rev() {
+        if i % 7 != 0 {
+            v1.add(i, i);
+        }
+    }
+    v1.remove(7001);
+
+    v1.update().par_join_all(|id, a| {
+        assert_eq!(id, *a);
+        *a *= 2;
+    });
+    v1.read().join_all(|id, a| assert_eq!(2 * id, *a));
+}
+
+#[test]
+fn test_par_row_join() {
+    init_test(module_path!());
+
+    let mut m1 = new_dmat::<usize>();
+    let mut v1 = new_dvec::<usize>();
+    for r in 0..3000 {
+        for c in 0..(r % 5) {
+            m1.add(r, c, r + c);
+        }
+        if r % 3 == 0 {
+            v1.add(r, r);
+        }
+    }
+
+    (v1.read(), m1.update()).par_join_all(|r, (v, row)| {
+        assert_eq!(r, *v);
+        row.join_all(|c, e| *e -= r + c);
+    });
+
+    let sum = AtomicUsize::new(0);
+    m1.read().par_join_all(|_, row| {
+        row.join_all(|_, e| {
+            sum.fetch_add(*e, Ordering::Relaxed);
+        });
+    });
+    let expected: usize = (0..3000)
+        .filter(|r| r % 3 != 0)
+        .map(|r| (0..(r % 5)).map(|c| r + c).sum::<usize>())
+        .sum();
+    assert_eq!(sum.load(Ordering::Relaxed), expected);
+}
+
+/// Check if a type implements IndexSplit using auto-ref based method resolution:
+/// the by-value impl is selected when the bound holds, the by-reference fallback otherwise.
+struct SplitProbe<T>(PhantomData<T>);
+
+trait IsSplit {
+    fn is_split(&self) -> bool {
+        true
+    }
+}
+
+impl<T: IndexSplit> IsSplit for SplitProbe<T> {}
+
+trait IsNotSplit {
+    fn is_split(&self) -> bool {
+        false
+    }
+}
+
+impl<T> IsNotSplit for &SplitProbe<T> {}
+
+#[test]
+fn test_par_tracked_join() {
+    init_test(module_path!());
+
+    debug!("tracked update is rejected by the parallel join");
+    assert!((&SplitProbe::<WrapUpdate<'_, DenseStore<usize>>>(PhantomData)).is_split());
+    assert!(!(&SplitProbe::<WrapUpdate<'_, TrackedStore<DenseStore<usize>>>>(PhantomData)).is_split());
+
+    debug!("tracked vectors can be read in parallel");
+    let mut tracked = new_dvec::<usize>().into_tracked();
+    let mut values = new_dvec::<usize>();
+    for i in 0..10000 {
+        tracked.add(i * 2, i);
+        values.add(i, 0);
+    }
+    tracked.drain_changes();
+    (tracked.read(), values.update()).par_join_all(|_, (t, v)| *v = *t);
+    assert!(tracked.changes().is_empty());
+
+    debug!("tracked update in a sequential join");
+    (values.read(), tracked.update()).join_all(|_, (v, t)| *t += *v);
+    assert_eq!(tracked.changed().join_count(), 5000);
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "serialize")]
+
+use log::debug;
+use quickcheck::quickcheck;
+use shine_graph::bits::{BitSet, BitSetView, BitSetViewExt};
+use shine_graph::join::{IntoJoin, IntoJoinExt};
+use shine_graph::smat::{new_dmat, new_hdmat, SDMatrix, SHDMatrix};
+use shine_graph::svec::{new_dvec, new_hvec, new_tvec, SDVector, SSVector, STVector};
+use shine_testutils::{init_quickcheck_test, init_test};
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_bitset_serialize() {
+    init_test(module_path!());
+
+    let mut set = BitSet::<u8>::new_with_capacity(100);
+    for &i in &[0, 1, 2, 3, 7, 8, 9, 31, 32, 64, 65, 66, 99] {
+        set.add(i);
+    }
+
+    let json = serde_json::to_string(&set).unwrap();
+    debug!("json: {}", json);
+    assert!(json.contains("[0,4]"));
+    assert!(json.contains("[7,3]"));
+
+    let copy: BitSet<u8> = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy.capacity(), set.capacity());
+    assert_eq!(copy.number_of_set(), set.number_of_set());
+    assert_eq!(copy.iter().collect::<Vec<_>>(), set.iter().collect::<Vec<_>>());
+
+    assert!(serde_json::from_str::<BitSet<u8>>(r#"{"capacity":8,"runs":[[6,3]]}"#).is_err());
+    assert!(serde_json::from_str::<BitSet<u8>>(r#"{"capacity":18446744073709551615,"runs":[]}"#).is_err());
+    assert!(
+        serde_json::from_str::<SDVector<u32>>(r#"{"mask":{"capacity":18446744073709551615,"runs":[]},"values":[]}"#).is_err()
+    );
+    assert!(serde_json::from_str::<BitSet<u8>>(r#"{"capacity":4294967296,"runs":[[0,1]]}"#).is_err());
+    assert!(
+        serde_json::from_str::<SDVector<u64>>(r#"{"mask":{"capacity":4294967296,"runs":[[4294967295,1]]},"values":[1]}"#)
+            .is_err()
+    );
+    assert!(serde_json::from_str::<SDMatrix<i32>>(
+        r#"{"rows":{"capacity":32,"runs":[[0,1]]},"row_lengths":[1],"columns":[4000000000000],"values":[1],"column_index":true}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<SDMatrix<i32>>(
+        r#"{"rows":{"capacity":32,"runs":[[0,1]]},"row_lengths":[1],"columns":[18446744073709551615],"values":[1],"column_index":false}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<SDMatrix<i32>>(
+        r#"{"rows":{"capacity":10000032,"runs":[[10000000,1]]},"row_lengths":[1],"columns":[0],"values":[1],"column_index":false}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn quickcheck_bitset_serialize() {
+    init_quickcheck_test(module_path!(), 1000);
+
+    fn fuzzer(bits: Vec<u16>) -> bool {
+        let mut set = BitSet::<u32>::new();
+        for &b in &bits {
+            set.increase_capacity_to(b as usize + 1);
+            set.add(b as usize);
+        }
+
+        let copy = round_trip(&set);
+        copy.capacity() == set.capacity()
+            && copy.number_of_set() == set.number_of_set()
+            && copy.iter().eq(set.iter())
+            && (0..set.get_level_count()).all(|l| copy.get_level(l) == set.get_level(l))
+    }
+
+    quickcheck(fuzzer as fn(Vec<u16>) -> bool);
+}
+
+#[test]
+fn quickcheck_svec_serialize() {
+    init_quickcheck_test(module_path!(), 1000);
+
+    fn fuzzer(items: Vec<(u16, u32)>) -> bool {
+        let mut dvec = new_dvec();
+        let mut hvec = new_hvec();
+        let mut tvec = new_tvec();
+        for &(i, v) in &items {
+            dvec.add(i as usize, v);
+            hvec.add(i as usize, v);
+            tvec.add(i as usize, ());
+        }
+
+        let dcopy: SDVector<u32> = round_trip(&dvec);
+        let hcopy: SSVector<u32> = round_trip(&hvec);
+        let tcopy: STVector = round_trip(&tvec);
+
+        let all = (dvec.read(), dcopy.read(), hcopy.read(), tcopy.read()).join_count();
+        let same = (dvec.read(), dcopy.read(), hcopy.read())
+            .into_join()
+            .all(|(_, (a, b, c))| a == b && a == c);
+        all == dvec.nnz()
+            && dcopy.nnz() == dvec.nnz()
+            && hcopy.nnz() == dvec.nnz()
+            && tcopy.nnz() == dvec.nnz()
+            && dcopy.capacity() == dvec.capacity()
+            && same
+    }
+
+    quickcheck(fuzzer as fn(Vec<(u16, u32)>) -> bool);
+}
+
+#[test]
+fn quickcheck_smat_serialize() {
+    init_quickcheck_test(module_path!(), 1000);
+
+    fn fuzzer(items: Vec<(u8, u8, u32)>, column_index: bool) -> bool {
+        let mut dmat = new_dmat();
+        let mut hmat = new_hdmat();
+        for &(r, c, v) in &items {
+            dmat.add(r as usize, c as usize, v);
+            hmat.add(r as usize, c as usize, v);
+        }
+        if column_index {
+            dmat = dmat.with_column_index(Default::default());
+        }
+
+        let dcopy: SDMatrix<u32> = round_trip(&dmat);
+        let hcopy: SHDMatrix<u32> = round_trip(&hmat);
+
+        let mut count = 0;
+        let mut same = true;
+        (dmat.read(), dcopy.read(), hcopy.read()).join_all(|_, (a, b, c)| {
+            (a, b, c).join_all(|_, (a, b, c)| {
+                count += 1;
+                same &= a == b && a == c;
+            });
+        });
+
+        count == dmat.nnz()
+            && dcopy.nnz() == dmat.nnz()
+            && hcopy.nnz() == dmat.nnz()
+            && dcopy.capacity() == dmat.capacity()
+            && dcopy.has_column_index() == column_index
+            && same
+    }
+
+    quickcheck(fuzzer as fn(Vec<(u8, u8, u32)>, bool) -> bool);
+}
+
+#[test]
+fn test_smat_serialize_corrupted() {
+    init_test(module_path!());
+
+    assert!(serde_json::from_str::<SDMatrix<u32>>(
+        r#"{"rows":{"capacity":32,"runs":[[0,2]]},"row_lengths":[18446744073709551615,2],"columns":[0],"values":[1],"column_index":false}"#
+    )
+    .is_err());
+}
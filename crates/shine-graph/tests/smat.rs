@@ -2,7 +2,7 @@ use rand;
 
 use log::{debug, trace};
 use rand::Rng;
-use shine_graph::smat::{new_amat, new_dmat, MatrixMask, SMatrix, StoreMut};
+use shine_graph::smat::{new_amat, new_dmat, new_hamat, new_hdmat, MatrixMask, SMatrix, StoreMut};
 use shine_testutils::init_test;
 
 type Data = (usize, usize);
@@ -72,6 +72,10 @@ fn test_simple() {
     test_simple_(new_dmat::<Data>());
     debug!("SparseAMatrix");
     test_simple_(new_amat::<Data>());
+    debug!("SparseHDMatrix");
+    test_simple_(new_hdmat::<Data>());
+    debug!("SparseHAMatrix");
+    test_simple_(new_hamat::<Data>());
 }
 
 fn test_stress_<M: MatrixMask, S: StoreMut<Item = Data>>(mut matrix: SMatrix<M, S>, size: usize, cnt: usize) {
@@ -108,12 +112,18 @@ fn test_stress() {
 
     debug!("SparseDMatrix - big");
     test_stress_(new_dmat::<Data>(), 1024, 100000);
+    debug!("SparseHDMatrix - big");
+    test_stress_(new_hdmat::<Data>(), 1024, 100000);
 
     for _ in 0..10 {
         trace!("SparseDMatrix/CSMatrix");
         test_stress_(new_dmat::<Data>(), 128, 900);
         trace!("SparseAMatrix/CSMatrix");
         test_stress_(new_amat::<Data>(), 128, 900);
+        trace!("SparseHDMatrix/HCSMatrix");
+        test_stress_(new_hdmat::<Data>(), 128, 900);
+        trace!("SparseHAMatrix/HCSMatrix");
+        test_stress_(new_hamat::<Data>(), 128, 900);
     }
 }
 
@@ -163,4 +173,45 @@ fn test_data_iter() {
     test_data_iter_(new_dmat::<Data>());
     debug!("SparseAMatrix/CSMatrix");
     test_data_iter_(new_amat::<Data>());
+    debug!("SparseHDMatrix/HCSMatrix");
+    test_data_iter_(new_hdmat::<Data>());
+    debug!("SparseHAMatrix/HCSMatrix");
+    test_data_iter_(new_hamat::<Data>());
+}
+
+#[test]
+fn test_tall() {
+    init_test(module_path!());
+
+    let mut matrix = new_hdmat::<Data>();
+    let ids = [5_000_000, 17, 1_000_000, 123_456_789, 1_000_001];
+
+    for (i, &r) in ids.iter().enumerate() {
+        for &c in &ids[i..] {
+            assert_eq!(matrix.add(r, c, (r, c)), None);
+        }
+    }
+    assert_eq!(matrix.nnz(), 15);
+    for (i, &r) in ids.iter().enumerate() {
+        for (j, &c) in ids.iter().enumerate() {
+            if j >= i {
+                assert_eq!(matrix.get(r, c), Some(&(r, c)));
+            } else {
+                assert_eq!(matrix.get(r, c), None);
+            }
+        }
+    }
+
+    for &c in &ids {
+        matrix.remove(ids[0], c);
+    }
+    assert_eq!(matrix.nnz(), 10);
+    assert_eq!(matrix.get(ids[0], ids[0]), None);
+    assert_eq!(matrix.get(ids[1], ids[4]), Some(&(ids[1], ids[4])));
+
+    // rows are stored in order
+    let data: Vec<_> = matrix.data_iter().map(|d| d.0).collect();
+    let mut sorted = data.clone();
+    sorted.sort();
+    assert_eq!(data, sorted);
 }
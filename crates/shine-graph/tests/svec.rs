@@ -3,7 +3,8 @@ use rand;
 use log::{debug, trace};
 use rand::Rng;
 
-use shine_graph::svec::{new_dvec, new_hvec, SVector, StoreMut};
+use shine_graph::join::IntoJoinExt;
+use shine_graph::svec::{new_dvec, new_hvec, new_pvec, SVector, StoreMut, TrackedSVector};
 use shine_testutils::init_test;
 
 type Data = usize;
@@ -88,6 +89,10 @@ fn test_simple() {
     test_simple_(new_dvec());
     debug!("SHVector");
     test_simple_(new_hvec());
+    debug!("SPVector");
+    test_simple_(new_pvec());
+    debug!("tracked SDVector");
+    test_simple_(new_dvec().into_tracked());
 }
 
 fn test_stress_<S: StoreMut<Item = Data>>(mut vector: SVector<S>, size: usize, cnt: usize) {
@@ -124,6 +129,8 @@ fn test_stress() {
         test_stress_(new_dvec(), 1024, 100000);
         trace!("SHVector");
         test_stress_(new_hvec(), 1024, 100000);
+        trace!("SPVector");
+        test_stress_(new_pvec(), 1024, 100000);
     }
 }
 
@@ -171,4 +178,138 @@ fn test_data_iter() {
     test_data_iter_(new_dvec::<Data>());
     debug!("SHVector");
     test_data_iter_(new_hvec::<Data>());
+    debug!("SPVector");
+    test_data_iter_(new_pvec::<Data>());
+    debug!("tracked SDVector");
+    test_data_iter_(new_dvec::<Data>().into_tracked());
+}
+
+fn check_changes<S: StoreMut>(vector: &TrackedSVector<S>, added: &[usize], removed: &[usize], changed: &[usize]) {
+    assert_eq!(vector.added().join_collect::<_, Vec<_>, _>(|id, _| id), added);
+    assert_eq!(vector.removed().join_collect::<_, Vec<_>, _>(|id, _| id), removed);
+    assert_eq!(vector.changed().join_collect::<_, Vec<_>, _>(|id, _| id), changed);
+}
+
+fn test_tracked_<S: StoreMut<Item = Data>>(vector: SVector<S>) {
+    let mut vector = vector.into_tracked();
+
+    debug!("add");
+    vector.add(1, 1);
+    vector.add(3, 3);
+    vector.add(5, 5);
+    vector.add(7, 7);
+    *vector.get_mut(3).unwrap() = 33;
+    check_changes(&vector, &[1, 3, 5, 7], &[], &[]);
+    assert_eq!(vector.drain_changes().added().join_count(), 4);
+    assert!(vector.changes().is_empty());
+
+    debug!("mutate");
+    *vector.get_mut(1).unwrap() = 11;
+    vector.get_entry(5).get_or(55);
+    vector.add(7, 77);
+    vector.add(9, 9);
+    check_changes(&vector, &[9], &[], &[1, 5, 7]);
+    vector.drain_changes();
+
+    debug!("update join");
+    {
+        let mut mask = new_dvec::<Data>();
+        mask.add(3, 0);
+        mask.add(9, 0);
+        (mask.read(), vector.update()).join_all(|_, (_, v)| *v += 1);
+    }
+    check_changes(&vector, &[], &[], &[3, 9]);
+    vector.drain_changes();
+
+    debug!("remove");
+    vector.remove(1);
+    vector.add(2, 2);
+    vector.remove(2);
+    vector.add(4, 4);
+    vector.get_entry(5).remove();
+    vector.add(5, 5);
+    *vector.get_mut(7).unwrap() = 7;
+    vector.remove(7);
+    check_changes(&vector, &[4], &[1, 7], &[5]);
+    vector.drain_changes();
+
+    debug!("clear");
+    vector.add(11, 11);
+    vector.clear();
+    check_changes(&vector, &[], &[3, 4, 5, 9], &[]);
+}
+
+#[test]
+fn test_tracked() {
+    init_test(module_path!());
+
+    debug!("SDVector");
+    test_tracked_(new_dvec::<Data>());
+    debug!("SHVector");
+    test_tracked_(new_hvec::<Data>());
+    debug!("SPVector");
+    test_tracked_(new_pvec::<Data>());
+
+    debug!("into tracked");
+    let mut vector = new_dvec::<Data>();
+    vector.add(1, 1);
+    let mut vector = vector.into_tracked();
+    assert!(vector.changes().is_empty());
+    vector.remove(1);
+    check_changes(&vector, &[], &[1], &[]);
+}
+
+#[test]
+fn test_packed() {
+    init_test(module_path!());
+
+    let mut vector = new_pvec::<Data>();
+    for i in 0..10 {
+        vector.add(i * 3, i);
+    }
+    assert_eq!(vector.as_slice(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    debug!("swap remove");
+    assert_eq!(vector.remove(6), Some(2));
+    assert_eq!(vector.remove(0), Some(0));
+    assert_eq!(vector.remove(27), Some(9));
+    assert_eq!(vector.as_slice(), &[8, 1, 7, 3, 4, 5, 6]);
+    assert_eq!(vector.get(24), Some(&8));
+    assert_eq!(vector.get(3), Some(&1));
+    assert_eq!(vector.get(27), None);
+
+    debug!("bulk update");
+    for v in vector.as_mut_slice() {
+        *v *= 10;
+    }
+    let mut sum = 0;
+    vector.read().join_all(|id, v| {
+        assert_eq!(*v, id / 3 * 10);
+        sum += *v;
+    });
+    assert_eq!(sum, vector.as_slice().iter().sum::<usize>());
+
+    debug!("iterate with indices");
+    for (id, v) in vector.packed_iter_mut() {
+        *v += id;
+    }
+    assert!(vector.packed_iter().all(|(id, v)| *v == id / 3 * 10 + id));
+    assert_eq!(vector.packed_iter().count(), vector.nnz());
+
+    debug!("sort by index");
+    vector.sort_by_index();
+    assert_eq!(vector.as_slice(), &[13, 39, 52, 65, 78, 91, 104]);
+    assert_eq!(
+        vector.read().join_collect::<_, Vec<_>, _>(|id, v| (id, *v)),
+        vector.packed_iter().map(|(id, v)| (id, *v)).collect::<Vec<_>>()
+    );
+    assert_eq!(vector.remove(9), Some(39));
+    assert_eq!(vector.get(12), Some(&52));
+    vector.sort_by_index();
+    assert_eq!(vector.as_slice(), &[13, 52, 65, 78, 91, 104]);
+
+    vector.clear();
+    assert!(vector.as_slice().is_empty());
+    vector.add(6, 6);
+    assert_eq!(vector.as_slice(), &[6]);
 }
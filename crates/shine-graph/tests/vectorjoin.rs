@@ -1,6 +1,6 @@
 use log::debug;
 
-use shine_graph::join::IntoJoinExt;
+use shine_graph::join::{maybe, not, IntoJoin, IntoJoinExt};
 use shine_graph::svec::{new_dvec, new_tvec};
 use shine_testutils::init_test;
 
@@ -90,3 +90,156 @@ fn test_svec_join() {
         assert_eq!(whole_string, ",(3,5,Some(())),(14,16,None),(17,19,Some(())),(18,20,None)");
     }
 }
+
+#[test]
+fn test_join_iterator() {
+    init_test(module_path!());
+
+    let mut v1 = new_dvec::<usize>();
+    let mut v2 = new_dvec::<usize>();
+    for i in 0..20 {
+        v1.add(i * 2, i);
+        v2.add(i * 3, i);
+    }
+
+    debug!("iterator");
+    {
+        let items: Vec<_> = (v1.read(), v2.read()).into_join().map(|(id, (a, b))| (id, *a, *b)).collect();
+        assert_eq!(
+            items,
+            vec![
+                (0, 0, 0),
+                (6, 3, 2),
+                (12, 6, 4),
+                (18, 9, 6),
+                (24, 12, 8),
+                (30, 15, 10),
+                (36, 18, 12)
+            ]
+        );
+
+        let sum: usize = v1.read().into_join().filter(|(id, _)| id % 4 == 0).map(|(_, a)| *a).sum();
+        assert_eq!(sum, (0..20).filter(|i| i % 2 == 0).sum());
+
+        // items of a read-only join can outlive the next call
+        let mut join = v1.read().into_join();
+        let first = join.next().unwrap();
+        let second = join.next().unwrap();
+        assert_eq!((first.0, *first.1, second.0, *second.1), (0, 0, 2, 1));
+        assert_eq!(join.count(), 18);
+    }
+
+    debug!("helpers");
+    {
+        assert_eq!((v1.read(), v2.read()).join_count(), 7);
+        assert_eq!((v1.update(), v2.read()).join_count(), 7);
+
+        let (id, (a, _)) = (v1.update(), v2.read()).join_first().unwrap();
+        assert_eq!(id, 0);
+        *a = 100;
+        assert_eq!(v1.get(0), Some(&100));
+
+        let found = (v1.update(), v2.read()).join_find(|_, (_, b)| **b > 3).map(|(id, _)| id);
+        assert_eq!(found, Some(12));
+        assert_eq!(
+            (v1.update(), v2.read()).join_find(|_, (_, b)| **b > 100).map(|(id, _)| id),
+            None
+        );
+
+        let ids: Vec<usize> = (v1.update(), v2.read()).join_collect(|id, (a, b)| {
+            *a += *b;
+            id
+        });
+        assert_eq!(ids, vec![0, 6, 12, 18, 24, 30, 36]);
+        assert_eq!(v1.get(6), Some(&5));
+
+        let odd: Vec<usize> = v2
+            .read()
+            .join_filter_collect(|id, b| if b % 2 == 1 { Some(id) } else { None });
+        assert_eq!(odd, (0..20).filter(|i| i % 2 == 1).map(|i| i * 3).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_maybe_not_join() {
+    init_test(module_path!());
+
+    let mut transform = new_dvec::<usize>();
+    let mut velocity = new_dvec::<usize>();
+    let mut frozen = new_tvec();
+    for i in 0..10 {
+        transform.add(i, i);
+    }
+    velocity.add(2, 20);
+    velocity.add(3, 30);
+    velocity.add(7, 70);
+    velocity.add(12, 120);
+    frozen.add(3, ());
+    frozen.add(4, ());
+    frozen.add(15, ());
+
+    debug!("maybe");
+    {
+        let items: Vec<_> = (transform.read(), maybe(velocity.read()))
+            .into_join()
+            .map(|(id, (_, v))| (id, v.cloned()))
+            .collect();
+        assert_eq!(items.len(), 10);
+        assert_eq!(items[1], (1, None));
+        assert_eq!(items[2], (2, Some(20)));
+        assert_eq!(items[7], (7, Some(70)));
+    }
+
+    debug!("not");
+    {
+        let ids: Vec<usize> = (transform.read(), not(frozen.read())).join_collect(|id, _| id);
+        assert_eq!(ids, vec![0, 1, 2, 5, 6, 7, 8, 9]);
+
+        let ids: Vec<usize> = (velocity.read(), not(transform.read())).join_collect(|id, _| id);
+        assert_eq!(ids, vec![12]);
+    }
+
+    debug!("maybe and not");
+    {
+        (transform.update(), maybe(velocity.read()), not(frozen.read())).join_all(|_, (t, v, _)| {
+            if let Some(v) = v {
+                *t += *v;
+            }
+        });
+        let items: Vec<_> = transform.read().into_join().map(|(id, t)| (id, *t)).collect();
+        assert_eq!(
+            items,
+            vec![
+                (0, 0),
+                (1, 1),
+                (2, 22),
+                (3, 3),
+                (4, 4),
+                (5, 5),
+                (6, 6),
+                (7, 77),
+                (8, 8),
+                (9, 9)
+            ]
+        );
+
+        let sum: usize = (maybe(velocity.read()), not(frozen.read()), transform.read())
+            .into_join()
+            .filter_map(|(_, (v, _, _))| v.cloned())
+            .sum();
+        assert_eq!(sum, 90);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Join has no bounded source")]
+fn test_unbounded_join() {
+    init_test(module_path!());
+
+    let mut v1 = new_dvec::<usize>();
+    let mut v2 = new_tvec();
+    v1.add(1, 1);
+    v2.add(2, ());
+
+    (maybe(v1.read()), not(v2.read())).join_all(|_, _| {});
+}
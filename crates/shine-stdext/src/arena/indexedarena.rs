@@ -10,10 +10,36 @@ enum Entry<T> {
     Occupied(T),
 }
 
+struct Slot<T> {
+    // Incremented each time the slot is freed to invalidate the outstanding handles
+    generation: u32,
+    entry: Entry<T>,
+}
+
+/// Generation checked index of an item in an IndexedArena.
+/// Once the referenced item is deallocated, the handle is invalidated and it won't alias the item
+/// reusing the same slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    /// Returns the raw index of the referenced slot
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
 /// Arena allocator
 pub struct IndexedArena<T> {
     size: usize,
-    items: Vec<Entry<T>>,
+    items: Vec<Slot<T>>,
     free_head: Entry<T>,
     increment: usize,
 }
@@ -47,7 +73,15 @@ impl<T> IndexedArena<T> {
         for id in (start_length..self.items.len()).rev() {
             assert!(if let Entry::Vacant(_) = self.free_head { true } else { false });
             let head = mem::replace(&mut self.free_head, Entry::Vacant(id));
-            unsafe { ptr::write(&mut self.items[id], head) };
+            unsafe {
+                ptr::write(
+                    &mut self.items[id],
+                    Slot {
+                        generation: 0,
+                        entry: head,
+                    },
+                )
+            };
         }
     }
 
@@ -96,9 +130,9 @@ impl<T> IndexedArena<T> {
         } else {
             unreachable!()
         };
-        self.free_head = mem::replace(&mut self.items[id], Entry::Occupied(data));
+        self.free_head = mem::replace(&mut self.items[id].entry, Entry::Occupied(data));
         assert!(if let Entry::Vacant(_) = self.free_head { true } else { false });
-        if let Entry::Occupied(ref mut data) = &mut self.items[id] {
+        if let Entry::Occupied(ref mut data) = &mut self.items[id].entry {
             (id, data)
         } else {
             unreachable!()
@@ -106,21 +140,128 @@ impl<T> IndexedArena<T> {
     }
 
     pub fn deallocate(&mut self, id: usize) -> T {
+        if let Entry::Vacant(_) = self.items[id].entry {
+            panic!("Invalid index")
+        }
         self.size -= 1;
         let head = mem::replace(&mut self.free_head, Entry::Vacant(id));
-        let data = mem::replace(&mut self.items[id], head);
-        if let Entry::Occupied(data) = data {
+        let slot = &mut self.items[id];
+        slot.generation = slot.generation.wrapping_add(1);
+        if let Entry::Occupied(data) = mem::replace(&mut slot.entry, head) {
             data
         } else {
-            panic!("Invalid index")
+            unreachable!()
         }
     }
 
     pub fn clear(&mut self) {
+        // drop all the items and relink all the slots as free, generation is incremented for the
+        // released items to invalidate the handles
         self.size = 0;
-        self.items.clear();
         self.free_head = Entry::Vacant(usize::max_value());
-        self.reserve(0); // relink all the allocated items in the veactor as free
+        for (id, slot) in self.items.iter_mut().enumerate().rev() {
+            if let Entry::Occupied(_) = slot.entry {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            slot.entry = mem::replace(&mut self.free_head, Entry::Vacant(id));
+        }
+    }
+
+    /// Allocates a new item and returns the generation checked handle to it.
+    pub fn insert(&mut self, data: T) -> Handle {
+        let index = self.allocate(data).0;
+        Handle {
+            index,
+            generation: self.items[index].generation,
+        }
+    }
+
+    /// Removes the item referenced by the handle. If handle is not valid, None is returned.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        if self.contains(handle) {
+            Some(self.deallocate(handle.index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the handle of an allocated item by the raw index.
+    pub fn handle_of(&self, id: usize) -> Option<Handle> {
+        match self.items.get(id) {
+            Some(Slot {
+                generation,
+                entry: Entry::Occupied(_),
+            }) => Some(Handle {
+                index: id,
+                generation: *generation,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns if the handle references a live item.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        match self.items.get(handle.index) {
+            Some(Slot {
+                generation,
+                entry: Entry::Occupied(ref data),
+            }) if *generation == handle.generation => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.items.get_mut(handle.index) {
+            Some(Slot {
+                generation,
+                entry: Entry::Occupied(ref mut data),
+            }) if *generation == handle.generation => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the allocated items and their handles
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.items.iter().enumerate(),
+        }
+    }
+
+    /// Iterate over the allocated items and their handles with mutable access
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            iter: self.items.iter_mut().enumerate(),
+        }
+    }
+
+    /// Keep only the items specified by the predicate.
+    /// In other words, remove all items such that f(handle, &mut data) returns false.
+    pub fn retain<F: FnMut(Handle, &mut T) -> bool>(&mut self, mut f: F) {
+        for id in 0..self.items.len() {
+            let keep = match self.items[id] {
+                Slot {
+                    generation,
+                    entry: Entry::Occupied(ref mut data),
+                } => f(Handle { index: id, generation }, data),
+                _ => true,
+            };
+            if !keep {
+                self.deallocate(id);
+            }
+        }
+    }
+
+    /// Remove all the items and return them with their (invalidated) handles in an iterator.
+    /// If the iterator is dropped before consumed, the remaining items are also removed.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            arena: self,
+            position: 0,
+        }
     }
 }
 
@@ -134,7 +275,7 @@ impl<T> ops::Index<usize> for IndexedArena<T> {
     type Output = T;
 
     fn index(&self, idx: usize) -> &Self::Output {
-        if let Entry::Occupied(ref data) = &self.items[idx] {
+        if let Entry::Occupied(ref data) = &self.items[idx].entry {
             data
         } else {
             panic!()
@@ -144,7 +285,7 @@ impl<T> ops::Index<usize> for IndexedArena<T> {
 
 impl<T> ops::IndexMut<usize> for IndexedArena<T> {
     fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
-        if let Entry::Occupied(ref mut data) = &mut self.items[idx] {
+        if let Entry::Occupied(ref mut data) = &mut self.items[idx].entry {
             data
         } else {
             panic!()
@@ -152,6 +293,20 @@ impl<T> ops::IndexMut<usize> for IndexedArena<T> {
     }
 }
 
+impl<T> ops::Index<Handle> for IndexedArena<T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle) -> &Self::Output {
+        self.get(handle).expect("Invalid handle")
+    }
+}
+
+impl<T> ops::IndexMut<Handle> for IndexedArena<T> {
+    fn index_mut(&mut self, handle: Handle) -> &mut Self::Output {
+        self.get_mut(handle).expect("Invalid handle")
+    }
+}
+
 impl<T> fmt::Debug for IndexedArena<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         let free = if let Entry::Vacant(id) = self.free_head {
@@ -170,12 +325,12 @@ impl<T> fmt::Debug for IndexedArena<T> {
 
         write!(f, "[ ")?;
         for v in &self.items {
-            match v {
+            match v.entry {
                 Entry::Vacant(id) => {
                     write!(f, "{} ", id)?;
                 }
                 _ => {
-                    write!(f, "DATA ")?;
+                    write!(f, "DATA({}) ", v.generation)?;
                 }
             }
         }
@@ -184,3 +339,68 @@ impl<T> fmt::Debug for IndexedArena<T> {
         Ok(())
     }
 }
+
+/// Iterator over the (handle, &item) pairs of an IndexedArena
+pub struct Iter<'a, T> {
+    iter: std::iter::Enumerate<std::slice::Iter<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.iter {
+            if let Entry::Occupied(ref data) = slot.entry {
+                let generation = slot.generation;
+                return Some((Handle { index, generation }, data));
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the (handle, &mut item) pairs of an IndexedArena
+pub struct IterMut<'a, T> {
+    iter: std::iter::Enumerate<std::slice::IterMut<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Handle, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.iter {
+            if let Entry::Occupied(ref mut data) = slot.entry {
+                let generation = slot.generation;
+                return Some((Handle { index, generation }, data));
+            }
+        }
+        None
+    }
+}
+
+/// Draining iterator of an IndexedArena
+pub struct Drain<'a, T> {
+    arena: &'a mut IndexedArena<T>,
+    position: usize,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = (Handle, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.arena.items.len() {
+            let index = self.position;
+            self.position += 1;
+            if let Some(handle) = self.arena.handle_of(index) {
+                return Some((handle, self.arena.deallocate(index)));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
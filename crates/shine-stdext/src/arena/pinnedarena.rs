@@ -1,40 +1,274 @@
-use log::trace;
-use std::marker::PhantomData;
+use log::{debug, trace};
+use std::fmt;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+
+/// Default number of items in a page
+pub const DEFAULT_PAGE_SIZE: usize = 256;
+
+/// A memory slot of a page. The stored value is the first field, thus a pointer to the value
+/// can be converted into a pointer to the slot.
+#[repr(C)]
+struct Slot<T> {
+    value: MaybeUninit<T>,
+    /// Index of the owning page
+    page: usize,
+    /// Next free slot if this slot is not occupied
+    next_free: *mut Slot<T>,
+    occupied: bool,
+}
+
+/// A continuous block of slots. Pages are never reallocated, thus the address of the slots are stable.
+struct Page<T> {
+    slots: Box<[Slot<T>]>,
+    /// Number of occupied slots
+    live: usize,
+}
+
+impl<T> Page<T> {
+    fn new(page_id: usize, page_size: usize) -> Page<T> {
+        let slots = (0..page_size)
+            .map(|_| Slot {
+                value: MaybeUninit::uninit(),
+                page: page_id,
+                next_free: ptr::null_mut(),
+                occupied: false,
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+        Page { slots, live: 0 }
+    }
+
+    /// Check if the pointer addresses a slot of this page using only the address.
+    fn contains(&self, slot: *const Slot<T>) -> bool {
+        let start = self.slots.as_ptr() as usize;
+        let end = start + self.slots.len() * mem::size_of::<Slot<T>>();
+        let addr = slot as usize;
+        start <= addr && addr < end && (addr - start) % mem::size_of::<Slot<T>>() == 0
+    }
+}
+
+impl<T> Drop for Page<T> {
+    fn drop(&mut self) {
+        if self.live == 0 {
+            return;
+        }
+        for slot in self.slots.iter_mut() {
+            if slot.occupied {
+                slot.occupied = false;
+                unsafe { ptr::drop_in_place(slot.value.as_mut_ptr()) };
+            }
+        }
+    }
+}
+
+/// Statistics of a PinnedArena
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArenaStatistics {
+    /// Number of items in a page
+    pub page_size: usize,
+    /// Number of allocated pages
+    pub page_count: usize,
+    /// Number of pages without any allocated item
+    pub empty_page_count: usize,
+    /// Number of allocated items
+    pub live: usize,
+    /// Maximum number of items without allocating a new page
+    pub capacity: usize,
+}
+
+impl ArenaStatistics {
+    /// Ratio of the unused slots that cannot be returned to the system to the total capacity.
+    /// The free slots of the completely empty pages are not considered as they can be released by a shrink.
+    pub fn fragmentation(&self) -> f32 {
+        if self.capacity == 0 {
+            return 0.;
+        }
+        let trapped = self.capacity - self.live - self.empty_page_count * self.page_size;
+        trapped as f32 / self.capacity as f32
+    }
+}
 
 /// Arena allocator that ensure stable memory location for the objects.
-/// todo: System memory is allocated in pages, but no memory is given back to the system.
-/// todo: The allocated memory is managed by the Arena using free list.
+/// System memory is allocated in pages and the freed slots are reused through a free list.
+/// Completely empty pages can be given back to the system by calling shrink.
 /// Arena has no concurrency handling. At most one thread may accessing the arean at a time, hence
 /// some synchronisation method have to be used in parallel environment.
 pub struct PinnedArena<T> {
+    page_size: usize,
+    pages: Vec<Page<T>>,
+    /// (start address, page id) of the pages sorted by the address
+    page_starts: Vec<(usize, usize)>,
+    free_head: *mut Slot<T>,
     size: usize,
-    _ph: PhantomData<T>,
 }
 
+unsafe impl<T: Send> Send for PinnedArena<T> {}
+
 impl<T> PinnedArena<T> {
     pub fn new() -> PinnedArena<T> {
+        Self::new_with_page_size(DEFAULT_PAGE_SIZE)
+    }
+
+    /// Creates a new arena with the given number of items in a page.
+    pub fn new_with_page_size(page_size: usize) -> PinnedArena<T> {
+        assert!(page_size > 0, "Page size must be positive");
         PinnedArena {
+            page_size,
+            pages: Vec::new(),
+            page_starts: Vec::new(),
+            free_head: ptr::null_mut(),
             size: 0,
-            _ph: PhantomData,
+        }
+    }
+
+    /// Creates a new arena with memory allocated for at least capacity items.
+    pub fn new_with_capacity(page_size: usize, capacity: usize) -> PinnedArena<T> {
+        let mut arena = Self::new_with_page_size(page_size);
+        arena.reserve(capacity);
+        arena
+    }
+
+    /// Allocates pages to have room for at least additional items without further allocation.
+    pub fn reserve(&mut self, additional: usize) {
+        let free = self.capacity() - self.size;
+        if free >= additional {
+            return;
+        }
+        let page_count = (additional - free + self.page_size - 1) / self.page_size;
+        for _ in 0..page_count {
+            self.add_page();
+        }
+    }
+
+    fn add_page(&mut self) {
+        let page_id = self.pages.len();
+        debug!("Allocating page {} with {} items", page_id, self.page_size);
+        self.pages.push(Page::new(page_id, self.page_size));
+        let page = self.pages.last_mut().unwrap();
+        let start = page.slots.as_ptr() as usize;
+        let idx = match self.page_starts.binary_search(&(start, page_id)) {
+            Ok(idx) | Err(idx) => idx,
+        };
+        self.page_starts.insert(idx, (start, page_id));
+        for slot in page.slots.iter_mut().rev() {
+            slot.next_free = self.free_head;
+            self.free_head = slot as *mut Slot<T>;
         }
     }
 
     pub fn allocate(&mut self, data: T) -> &mut T {
+        if self.free_head.is_null() {
+            self.add_page();
+        }
+
+        let slot = unsafe { &mut *self.free_head };
+        assert!(!slot.occupied);
+        self.free_head = mem::replace(&mut slot.next_free, ptr::null_mut());
+        slot.occupied = true;
+        slot.value = MaybeUninit::new(data);
+        self.pages[slot.page].live += 1;
         self.size += 1;
         trace!("size after allocation: {}", self.size);
-        let b = Box::new(data);
-        unsafe { &mut *Box::into_raw(b) }
+
+        unsafe { &mut *slot.value.as_mut_ptr() }
     }
 
     pub fn deallocate(&mut self, data: &mut T) {
+        let slot = data as *mut T as *mut Slot<T>;
+        let page_id = self.find_page(slot).expect("Item is not owned by this arena");
+        // the slot is owned by this arena, it is safe to access it
+        let slot = unsafe { &mut *slot };
+        debug_assert_eq!(slot.page, page_id);
+        assert!(slot.occupied, "Item is already deallocated");
+
+        slot.occupied = false;
+        unsafe { ptr::drop_in_place(slot.value.as_mut_ptr()) };
+        slot.next_free = self.free_head;
+        self.free_head = slot as *mut Slot<T>;
+        self.pages[page_id].live -= 1;
         self.size -= 1;
         trace!("size after deallocation: {}", self.size);
-        unsafe { Box::from_raw(data as *mut T) };
+    }
+
+    /// Find the page owning the slot by a binary search on the start addresses of the pages.
+    fn find_page(&self, slot: *const Slot<T>) -> Option<usize> {
+        let addr = slot as usize;
+        let idx = match self.page_starts.binary_search_by_key(&addr, |&(start, _)| start) {
+            Ok(idx) => idx,
+            Err(0) => return None,
+            Err(idx) => idx - 1,
+        };
+        let page_id = self.page_starts[idx].1;
+        if self.pages[page_id].contains(slot) {
+            Some(page_id)
+        } else {
+            None
+        }
+    }
+
+    /// Release the completely empty pages to the system and return the number of the released pages.
+    pub fn shrink(&mut self) -> usize {
+        let page_count = self.pages.len();
+        self.pages.retain(|page| page.live > 0);
+        let released = page_count - self.pages.len();
+        if released == 0 {
+            return 0;
+        }
+        debug!("Released {} pages", released);
+
+        // page ids have changed and the free list may reference released slots, rebuild them
+        self.page_starts = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(page_id, page)| (page.slots.as_ptr() as usize, page_id))
+            .collect();
+        self.page_starts.sort_unstable();
+        self.free_head = ptr::null_mut();
+        for (page_id, page) in self.pages.iter_mut().enumerate().rev() {
+            for slot in page.slots.iter_mut().rev() {
+                slot.page = page_id;
+                if !slot.occupied {
+                    slot.next_free = self.free_head;
+                    self.free_head = slot as *mut Slot<T>;
+                }
+            }
+        }
+        released
     }
 
     pub fn is_empty(&self) -> bool {
         self.size == 0
     }
+
+    /// Returns the number of allocated items
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of items that can be allocated without allocating a new page
+    pub fn capacity(&self) -> usize {
+        self.pages.len() * self.page_size
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn statistics(&self) -> ArenaStatistics {
+        ArenaStatistics {
+            page_size: self.page_size,
+            page_count: self.pages.len(),
+            empty_page_count: self.pages.iter().filter(|page| page.live == 0).count(),
+            live: self.size,
+            capacity: self.capacity(),
+        }
+    }
 }
 
 impl<T> Default for PinnedArena<T> {
@@ -42,3 +276,15 @@ impl<T> Default for PinnedArena<T> {
         Self::new()
     }
 }
+
+impl<T> fmt::Debug for PinnedArena<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        writeln!(f, "{:?}", self.statistics())?;
+        write!(f, "[ ")?;
+        for page in &self.pages {
+            write!(f, "{} ", page.live)?;
+        }
+        writeln!(f, "]")?;
+        Ok(())
+    }
+}
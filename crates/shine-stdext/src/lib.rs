@@ -14,6 +14,8 @@ pub mod libconfig {
 
 pub mod arena;
 pub mod namedstore;
+pub mod retention;
+pub mod spscqueue;
 pub mod spscstate;
 pub mod stdext;
 pub mod time;
@@ -0,0 +1,167 @@
+use log::{debug, trace, warn};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use super::Data;
+use crate::libconfig;
+
+/// Loading state of an entry
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadState {
+    /// Entry is waiting to be sent to the loader
+    Pending,
+    /// Entry is being processed by a loader worker
+    Loading,
+    /// Entry is loaded, or it was created without a loader
+    Ready,
+    /// Loading has failed with the given error
+    Failed(String),
+}
+
+/// Trait to load the stored data from the key on a worker thread.
+/// Until the load is completed, the placeholder created by Data::from_key is stored.
+/// A panic during the load is reported as a failure of the entry, the worker is kept alive.
+pub trait Loader<D: Data>: 'static + Send + Sync {
+    fn load(&self, key: &D::Key) -> Result<D, String>;
+}
+
+impl<D, F> Loader<D> for F
+where
+    D: Data,
+    F: 'static + Send + Sync + Fn(&D::Key) -> Result<D, String>,
+{
+    fn load(&self, key: &D::Key) -> Result<D, String> {
+        self(key)
+    }
+}
+
+struct LoadRequest<K> {
+    id: usize,
+    key: K,
+}
+
+pub(crate) struct LoadResponse<D: Data> {
+    pub id: usize,
+    pub key: D::Key,
+    pub result: Result<D, String>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown"
+    }
+}
+
+/// Pool of worker threads processing the load requests.
+pub(crate) struct LoaderPool<D: Data> {
+    next_id: usize,
+    requests: Option<Sender<LoadRequest<D::Key>>>,
+    responses: Receiver<LoadResponse<D>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<D: Data> LoaderPool<D> {
+    /// Creates a new pool with the given number of workers.
+    /// If worker_count is 0, the count is determined by the libconfig::PREFERRED_THREAD_COUNT.
+    pub fn new<L: Loader<D>>(loader: L, worker_count: usize) -> LoaderPool<D>
+    where
+        D: 'static + Send,
+        D::Key: 'static,
+    {
+        let worker_count = match (worker_count, libconfig::PREFERRED_THREAD_COUNT) {
+            (0, 0) => num_cpus::get(),
+            (0, preferred) => preferred,
+            (count, _) => count,
+        };
+        debug!("Starting {} loader workers", worker_count);
+
+        let loader = Arc::new(loader);
+        let (request_sender, request_receiver) = mpsc::channel::<LoadRequest<D::Key>>();
+        let (response_sender, response_receiver) = mpsc::channel();
+        let request_receiver = Arc::new(Mutex::new(request_receiver));
+
+        let workers = (0..worker_count)
+            .map(|id| {
+                let loader = loader.clone();
+                let requests = request_receiver.clone();
+                let responses = response_sender.clone();
+                thread::Builder::new()
+                    .name(format!("loader-{}", id))
+                    .spawn(move || loop {
+                        let request = {
+                            let requests = requests.lock().unwrap();
+                            requests.recv()
+                        };
+                        let request = match request {
+                            Ok(request) => request,
+                            Err(_) => break,
+                        };
+
+                        trace!("Loading {:?}", request.key);
+                        let result = match panic::catch_unwind(AssertUnwindSafe(|| loader.load(&request.key))) {
+                            Ok(result) => result,
+                            Err(payload) => {
+                                let err = format!("Loader panicked: {}", panic_message(&*payload));
+                                warn!("Loading {:?} failed: {}", request.key, err);
+                                Err(err)
+                            }
+                        };
+                        let response = LoadResponse {
+                            id: request.id,
+                            key: request.key,
+                            result,
+                        };
+                        if responses.send(response).is_err() {
+                            break;
+                        }
+                    })
+                    .expect("Failed to start loader worker")
+            })
+            .collect();
+
+        LoaderPool {
+            next_id: 0,
+            requests: Some(request_sender),
+            responses: response_receiver,
+            workers,
+        }
+    }
+
+    /// Sends a new load request to the workers and returns the id of the request.
+    pub fn request(&mut self, key: D::Key) -> usize {
+        self.next_id = self.next_id.wrapping_add(1);
+        let id = self.next_id;
+        self.requests
+            .as_ref()
+            .unwrap()
+            .send(LoadRequest { id, key })
+            .expect("Loader workers have terminated");
+        id
+    }
+
+    /// Returns a completed request, if any, without blocking.
+    pub fn try_recv(&self) -> Option<LoadResponse<D>> {
+        match self.responses.try_recv() {
+            Ok(response) => Some(response),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => panic!("Loader workers have terminated"),
+        }
+    }
+}
+
+impl<D: Data> Drop for LoaderPool<D> {
+    fn drop(&mut self) {
+        // closing the request channel stops the workers
+        self.requests.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod loader;
+mod reload;
+mod store;
+
+pub use self::loader::*;
+pub use self::reload::*;
+pub use self::store::*;
@@ -0,0 +1,64 @@
+use log::trace;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::{Data, WriteGuard};
+
+/// Polling hot-reload support for stores keyed by file paths.
+/// The modification time of the files are recorded and the items are invalidated
+/// whenever the time has changed (or the file was created or removed) since the previous check.
+pub struct ModifiedTimeChecker<K> {
+    times: HashMap<K, Option<SystemTime>>,
+}
+
+impl<K> ModifiedTimeChecker<K>
+where
+    K: Clone + Eq + Hash + AsRef<Path>,
+{
+    pub fn new() -> ModifiedTimeChecker<K> {
+        ModifiedTimeChecker { times: HashMap::new() }
+    }
+
+    fn modified_time(key: &K) -> Option<SystemTime> {
+        fs::metadata(key.as_ref()).and_then(|m| m.modified()).ok()
+    }
+
+    /// Invalidates the items with modified files and returns the number of the invalidated items.
+    /// Items seen for the first time are not invalidated, only their modification time is recorded.
+    pub fn check<D>(&mut self, store: &mut WriteGuard<'_, D>) -> usize
+    where
+        D: Data<Key = K>,
+    {
+        let mut times = HashMap::with_capacity(self.times.len());
+        let previous_times = &mut self.times;
+
+        let count = store.invalidate_where(|key, _| {
+            let time = Self::modified_time(key);
+            let modified = match previous_times.remove(key) {
+                Some(previous) => previous != time,
+                None => false,
+            };
+            times.insert(key.clone(), time);
+            if modified {
+                trace!("File modified: {:?}", key.as_ref());
+            }
+            modified
+        });
+
+        // keys of the removed items are dropped
+        self.times = times;
+        count
+    }
+}
+
+impl<K> Default for ModifiedTimeChecker<K>
+where
+    K: Clone + Eq + Hash + AsRef<Path>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,697 @@
+use log::{debug, trace};
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::ops;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::loader::{LoadState, Loader, LoaderPool};
+use crate::arena::PinnedArena;
+use crate::retention::{self, DropUnused, RetentionPolicy, Unused};
+
+/// Data stored in the Store
+pub trait Data {
+    type Key: Clone + Send + Eq + Hash + fmt::Debug;
+
+    fn from_key(key: Self::Key) -> Self
+    where
+        Self: Sized;
+}
+
+/// Reference counted index to access stored items in O(1).
+/// Eventough index has a (mutable) reference to the data, to aquire it, a properly locked store is required.
+/// The referenced data is private implementation detail that shall never be exposed to the client.
+/// It is used only as an index and acessing the referenced data is not safe as other thread may update it.
+pub struct Index<D: Data>(*mut Entry<D>);
+
+unsafe impl<D: Data> Send for Index<D> {}
+unsafe impl<D: Data> Sync for Index<D> {}
+
+impl<D: Data> Index<D> {
+    fn new(entry: *mut Entry<D>) -> Index<D> {
+        assert!(!entry.is_null());
+        unsafe { &(*entry).ref_count.fetch_add(1, Ordering::Relaxed) };
+        Index(entry)
+    }
+}
+
+impl<D: Data> fmt::Debug for Index<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        assert!(!self.0.is_null());
+        // the referneced data cannot be debug formatted as
+        // 1. it is not required in the trait
+        // 2. and it might be modified by another thread (see the Index struct).
+        let rc = unsafe { &(*self.0).ref_count.load(Ordering::Relaxed) };
+        write!(f, "Index({:p}, rc:{})", self.0, rc)
+    }
+}
+
+impl<D: Data> PartialEq for Index<D> {
+    fn eq(&self, e: &Self) -> bool {
+        assert!(!self.0.is_null());
+        assert!(!e.0.is_null());
+        self.0 == e.0
+    }
+}
+
+impl<D: Data> Clone for Index<D> {
+    fn clone(&self) -> Index<D> {
+        assert!(!self.0.is_null());
+        unsafe { &(*self.0).ref_count.fetch_add(1, Ordering::Relaxed) };
+        Index(self.0)
+    }
+}
+
+impl<D: Data> Drop for Index<D> {
+    fn drop(&mut self) {
+        assert!(!self.0.is_null());
+        unsafe { &(*self.0).ref_count.fetch_sub(1, Ordering::Relaxed) };
+    }
+}
+
+/// An entry in the store.
+#[derive(Debug)]
+struct Entry<D: Data> {
+    /// Number of active Index (number of references) to this entry
+    ref_count: AtomicUsize,
+
+    /// The stored data
+    value: D,
+
+    /// Loading state of the data
+    state: LoadState,
+
+    /// Id of the last load request to match the responses
+    load_id: usize,
+
+    /// Incremented each time the data is reloaded
+    version: usize,
+
+    /// The last frame (drain) the entry was referenced
+    last_used: usize,
+}
+
+// Shared data storing the new (pending) items
+struct SharedData<D: Data> {
+    resources: HashMap<D::Key, *mut Entry<D>>,
+}
+
+impl<D: Data> SharedData<D> {
+    fn get(&self, k: &D::Key) -> Option<Index<D>> {
+        self.resources.get(k).map(|&v| Index::new(v))
+    }
+}
+
+// Shared data storing the active items those require exclusive access to be updated.
+struct ExclusiveData<D: Data> {
+    arena: PinnedArena<Entry<D>>,
+    requests: HashMap<D::Key, *mut Entry<D>>,
+    loader: Option<LoaderPool<D>>,
+    /// Keys of the entries waiting to be sent to the loader
+    pending_loads: Vec<D::Key>,
+    retention: Box<dyn RetentionPolicy<D>>,
+    /// Number of drains performed, used to track the last use of the entries
+    frame: usize,
+}
+
+impl<D: Data> ExclusiveData<D> {
+    fn get(&self, k: &D::Key) -> Option<Index<D>> {
+        self.requests.get(k).map(|&v| Index::new(v))
+    }
+
+    /// Marks an entry as stale. If there is a loader, entry is queued for loading, otherwise
+    /// it is recreated immediately.
+    fn invalidate(&mut self, k: &D::Key, entry: &mut Entry<D>) {
+        if self.loader.is_some() {
+            if entry.state != LoadState::Pending {
+                entry.state = LoadState::Pending;
+                self.pending_loads.push(k.clone());
+            }
+        } else {
+            entry.value = <D as Data>::from_key(k.clone());
+            entry.version += 1;
+        }
+    }
+
+    /// Adds a new item to the store
+    fn get_or_add(&mut self, k: &D::Key) -> Index<D> {
+        let arena = &mut self.arena;
+        let pending_loads = &mut self.pending_loads;
+        let has_loader = self.loader.is_some();
+        let frame = self.frame;
+        let entry = self.requests.entry(k.clone()).or_insert_with(|| {
+            let state = if has_loader {
+                pending_loads.push(k.clone());
+                LoadState::Pending
+            } else {
+                LoadState::Ready
+            };
+            let new_entry = arena.allocate(Entry {
+                ref_count: AtomicUsize::new(0),
+                value: <D as Data>::from_key(k.clone()),
+                state,
+                load_id: 0,
+                version: 0,
+                last_used: frame,
+            });
+            new_entry as *mut Entry<D>
+        });
+
+        Index::new(*entry)
+    }
+}
+
+/// Thread safe resource store.
+/// While the store is locked for reading, no resource can be updated, but new one can be created
+/// with a two phase storage policy
+/// - first the shared data is searched for an existing items (non-blocking)
+/// - the secondary mutex guarded data is used if the item's been already added (blocking)
+/// When the store is write locked
+/// - the items from the secondary storage are moved into the primary one
+/// - resources can be updated
+/// - resources can be dropped if the reference count is zero
+pub struct Store<D: Data> {
+    shared: RwLock<SharedData<D>>,
+    exclusive: Mutex<ExclusiveData<D>>,
+}
+
+unsafe impl<D: Data> Send for Store<D> {}
+
+unsafe impl<D: Data> Sync for Store<D> {}
+
+impl<D: Data> Store<D> {
+    pub fn new() -> Store<D> {
+        Store {
+            shared: RwLock::new(SharedData {
+                resources: HashMap::new(),
+            }),
+            exclusive: Mutex::new(ExclusiveData {
+                arena: PinnedArena::new(),
+                requests: HashMap::new(),
+                loader: None,
+                pending_loads: Vec::new(),
+                retention: Box::new(DropUnused),
+                frame: 0,
+            }),
+        }
+    }
+
+    /// Creates a new store with memory allocated for at least capacity items
+    pub fn new_with_capacity(page_size: usize, capacity: usize) -> Store<D> {
+        Store {
+            shared: RwLock::new(SharedData {
+                resources: HashMap::with_capacity(capacity),
+            }),
+            exclusive: Mutex::new(ExclusiveData {
+                arena: PinnedArena::new_with_capacity(page_size, capacity),
+                requests: HashMap::with_capacity(capacity),
+                loader: None,
+                pending_loads: Vec::new(),
+                retention: Box::new(DropUnused),
+                frame: 0,
+            }),
+        }
+    }
+
+    /// Creates a new store where the data is loaded asynchronously by the given loader.
+    /// The loading is performed by worker_count threads, if 0 is given the libconfig::PREFERRED_THREAD_COUNT
+    /// is used. Until the loading is completed, the placeholder created by Data::from_key is stored.
+    pub fn new_with_loader<L: Loader<D>>(loader: L, worker_count: usize) -> Store<D>
+    where
+        D: 'static + Send,
+        D::Key: 'static,
+    {
+        let store = Store::new();
+        store.exclusive.lock().unwrap().loader = Some(LoaderPool::new(loader, worker_count));
+        store
+    }
+
+    /// Sets the policy to decide which unreferenced items are released by the drains.
+    /// By default all the unreferenced items are released immediately.
+    pub fn with_retention_policy<P: 'static + RetentionPolicy<D>>(mut self, policy: P) -> Store<D> {
+        self.exclusive.get_mut().unwrap().retention = Box::new(policy);
+        self
+    }
+
+    /// Aquire read lock.
+    pub fn try_read(&self) -> Option<ReadGuard<'_, D>> {
+        let shared = self.shared.try_read().ok()?;
+        Some(ReadGuard {
+            shared,
+            exclusive: &self.exclusive,
+        })
+    }
+
+    /// Try to aquire write lock.
+    pub fn try_write(&self) -> Option<WriteGuard<'_, D>> {
+        let shared = self.shared.try_write().ok()?;
+        let locked_exclusive = self.exclusive.lock().ok()?;
+        Some(WriteGuard {
+            shared,
+            locked_exclusive,
+        })
+    }
+}
+
+impl<D: Data> Default for Store<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Data> Drop for Store<D> {
+    fn drop(&mut self) {
+        let shared = &mut *(self.shared.try_write().unwrap());
+        let exclusive = &mut *(self.exclusive.lock().unwrap());
+        // stop the workers, the pending responses are dropped
+        exclusive.loader.take();
+        let arena = &mut exclusive.arena;
+        let requests = &mut exclusive.requests;
+        let resources = &mut shared.resources;
+
+        resources.retain(|_, &mut v| {
+            let v = unsafe { &mut *v };
+            assert!(v.ref_count.load(Ordering::Relaxed) == 0, "resource leak");
+            arena.deallocate(v);
+            false
+        });
+
+        requests.retain(|_, &mut v| {
+            let v = unsafe { &mut *v };
+            assert!(v.ref_count.load(Ordering::Relaxed) == 0, "resource leak");
+            arena.deallocate(v);
+            false
+        });
+
+        assert!(resources.is_empty(), "Leaking resource");
+        assert!(requests.is_empty(), "Leaking requests");
+        assert!(arena.is_empty(), "Leaking arena, internal store error");
+    }
+}
+
+/// Guarded read access to a store
+pub struct ReadGuard<'a, D: Data> {
+    shared: RwLockReadGuard<'a, SharedData<D>>,
+    exclusive: &'a Mutex<ExclusiveData<D>>,
+}
+
+impl<'a, D: 'a + Data> ReadGuard<'a, D> {
+    /// Try to get the index of a resource by the key.
+    /// If the global container is not accessible (ex. update is in progress),
+    /// a null index is returned.
+    pub fn try_get(&self, k: &D::Key) -> Option<Index<D>> {
+        let shared = &self.shared;
+        let exclusive = &self.exclusive;
+
+        shared.get(k).or_else(|| {
+            if let Ok(exclusive) = exclusive.try_lock() {
+                exclusive.get(k)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn get_blocking(&self, k: &D::Key) -> Option<Index<D>> {
+        let shared = &self.shared;
+        let exclusive = &self.exclusive;
+
+        shared.get(k).or_else(|| {
+            let exclusive = exclusive.lock().unwrap();
+            exclusive.get(k)
+        })
+    }
+
+    pub fn get_or_add_blocking(&mut self, k: &D::Key) -> Index<D> {
+        let shared = &mut self.shared;
+        let exclusive = &mut self.exclusive;
+
+        shared.get(k).unwrap_or_else(|| {
+            let mut exclusive = exclusive.lock().unwrap();
+            exclusive.get_or_add(&k)
+        })
+    }
+
+    pub fn at(&self, index: &Index<D>) -> &D {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        &entry.value
+    }
+
+    /// Returns the loading state of an item.
+    pub fn load_state(&self, index: &Index<D>) -> &LoadState {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        &entry.state
+    }
+
+    /// Returns the version of an item. Version is incremented each time the data is reloaded.
+    pub fn version(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.version
+    }
+
+    /// Returns the number of the active Index referencing the item (including the given one).
+    pub fn ref_count(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.ref_count.load(Ordering::Relaxed)
+    }
+
+    /// Iterate over the active items. The pending (not yet finalized) requests are not visited
+    /// as they are not accessible without locking.
+    pub fn iter(&self) -> Iter<'_, D> {
+        Iter {
+            active: self.shared.resources.iter(),
+            pending: None,
+        }
+    }
+
+    /// Returns the number of active items. The pending (not yet finalized) requests are not counted.
+    pub fn summary(&self) -> Summary {
+        Summary::collect(&self.shared.resources, None)
+    }
+}
+
+impl<'a, 'i, D: 'a + Data> ops::Index<&'i Index<D>> for ReadGuard<'a, D> {
+    type Output = D;
+
+    fn index(&self, index: &Index<D>) -> &Self::Output {
+        self.at(index)
+    }
+}
+
+/// Guarded update access to a store
+pub struct WriteGuard<'a, D: Data> {
+    shared: RwLockWriteGuard<'a, SharedData<D>>,
+    locked_exclusive: MutexGuard<'a, ExclusiveData<D>>,
+}
+
+impl<'a, D: 'a + Data> WriteGuard<'a, D> {
+    pub fn get(&self, k: &D::Key) -> Option<Index<D>> {
+        let shared = &self.shared;
+        let exclusive = &self.locked_exclusive;
+
+        exclusive.get(k).or_else(|| shared.get(k))
+    }
+
+    pub fn get_or_add(&mut self, k: &D::Key) -> Index<D> {
+        let shared = &mut self.shared;
+        let exclusive = &mut self.locked_exclusive;
+
+        shared.get(k).unwrap_or_else(|| exclusive.get_or_add(&k))
+    }
+
+    /// Returns if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.locked_exclusive.requests.is_empty() && self.shared.resources.is_empty()
+    }
+
+    /// Move all new (pending) resources into the active resources.
+    /// If the store has a loader, the completed loads are applied and the pending entries are sent to the workers.
+    pub fn finalize_requests(&mut self) {
+        self.shared.resources.extend(&mut self.locked_exclusive.requests.drain());
+        self.process_loads();
+    }
+
+    fn process_loads(&mut self) {
+        let resources = &self.shared.resources;
+        let exclusive = &mut *self.locked_exclusive;
+        let loader = match exclusive.loader {
+            Some(ref mut loader) => loader,
+            None => return,
+        };
+
+        while let Some(response) = loader.try_recv() {
+            let entry = match resources.get(&response.key) {
+                Some(&entry) => unsafe { &mut *entry },
+                None => {
+                    trace!("Dropping load response of a removed entry: {:?}", response.key);
+                    continue;
+                }
+            };
+            if entry.state != LoadState::Loading || entry.load_id != response.id {
+                trace!("Dropping outdated load response: {:?}", response.key);
+                continue;
+            }
+            match response.result {
+                Ok(value) => {
+                    entry.value = value;
+                    entry.state = LoadState::Ready;
+                    entry.version += 1;
+                }
+                Err(err) => {
+                    debug!("Failed to load {:?}: {}", response.key, err);
+                    entry.state = LoadState::Failed(err);
+                }
+            }
+        }
+
+        for key in exclusive.pending_loads.drain(..) {
+            // entry might have been drained in the meantime
+            if let Some(&entry) = resources.get(&key) {
+                let entry = unsafe { &mut *entry };
+                if entry.state == LoadState::Pending {
+                    entry.load_id = loader.request(key);
+                    entry.state = LoadState::Loading;
+                }
+            }
+        }
+    }
+
+    /// Marks the item with the given key as stale and reloads it in place, the indices remain valid.
+    /// If the store has a loader, the item is sent to the workers by the next finalize_requests and
+    /// the current data is kept until the loading completes. Without a loader, the data is recreated immediately.
+    /// Returns if the key was found.
+    pub fn invalidate(&mut self, k: &D::Key) -> bool {
+        let resources = &self.shared.resources;
+        let exclusive = &mut *self.locked_exclusive;
+        let entry = exclusive.requests.get(k).or_else(|| resources.get(k)).cloned();
+        match entry {
+            Some(entry) => {
+                exclusive.invalidate(k, unsafe { &mut *entry });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Invalidates all the items specified by the predicate and returns the number of the invalidated items.
+    /// In other words, reload all items such that f(&key, &data) returns true.
+    pub fn invalidate_where<F: FnMut(&D::Key, &D) -> bool>(&mut self, mut f: F) -> usize {
+        let exclusive = &mut *self.locked_exclusive;
+        let stale: Vec<_> = self
+            .shared
+            .resources
+            .iter()
+            .chain(exclusive.requests.iter())
+            .filter(|(k, &entry)| f(k, unsafe { &(*entry).value }))
+            .map(|(k, &entry)| (k.clone(), entry))
+            .collect();
+
+        for (k, entry) in stale.iter() {
+            exclusive.invalidate(k, unsafe { &mut **entry });
+        }
+        stale.len()
+    }
+
+    /// Returns the number of entries those are either waiting for or being processed by the loader.
+    pub fn pending_load_count(&self) -> usize {
+        self.summary().loading
+    }
+
+    fn collect_unused<F: FnMut(&mut D) -> bool>(
+        retention: &dyn RetentionPolicy<D>,
+        frame: usize,
+        v: &HashMap<D::Key, *mut Entry<D>>,
+        is_request: bool,
+        filter: &mut F,
+        candidates: &mut Vec<(Unused, (bool, D::Key))>,
+    ) {
+        for (k, &e) in v.iter() {
+            let e = unsafe { &mut *e };
+            if e.ref_count.load(Ordering::Relaxed) != 0 {
+                e.last_used = frame;
+            } else if filter(&mut e.value) {
+                let unused = Unused {
+                    last_used: e.last_used,
+                    cost: retention.cost(&e.value),
+                };
+                candidates.push((unused, (is_request, k.clone())));
+            }
+        }
+    }
+
+    /// Drain unreferenced elements those specified by the predicate.
+    /// In other words, remove all unreferenced resources such that f(&mut data) returns true and
+    /// the retention policy of the store allows the eviction.
+    /// Each drain advances the frame counter used to track the last use of the items.
+    pub fn drain_unused_filtered<F: FnMut(&mut D) -> bool>(&mut self, mut filter: F) {
+        let exclusive = &mut *self.locked_exclusive;
+        let resources = &mut self.shared.resources;
+        exclusive.frame += 1;
+        let frame = exclusive.frame;
+
+        let mut candidates = Vec::new();
+        Self::collect_unused(&*exclusive.retention, frame, resources, false, &mut filter, &mut candidates);
+        Self::collect_unused(
+            &*exclusive.retention,
+            frame,
+            &exclusive.requests,
+            true,
+            &mut filter,
+            &mut candidates,
+        );
+
+        for (is_request, k) in retention::select_evicted(&mut *exclusive.retention, frame, candidates) {
+            let entry = if is_request {
+                exclusive.requests.remove(&k)
+            } else {
+                resources.remove(&k)
+            };
+            exclusive.arena.deallocate(unsafe { &mut *entry.unwrap() });
+        }
+    }
+
+    /// Drain all unreferenced items permitted by the retention policy.
+    /// With the default policy only the referenced items are kept in the store.
+    pub fn drain_unused(&mut self) {
+        self.drain_unused_filtered(|_| true)
+    }
+
+    pub fn at(&self, index: &Index<D>) -> &D {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        &entry.value
+    }
+
+    pub fn at_mut(&mut self, index: &Index<D>) -> &mut D {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &mut (*index.0) };
+        &mut entry.value
+    }
+
+    /// Returns the loading state of an item.
+    pub fn load_state(&self, index: &Index<D>) -> &LoadState {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        &entry.state
+    }
+
+    /// Returns the version of an item. Version is incremented each time the data is reloaded.
+    pub fn version(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.version
+    }
+
+    /// Returns the number of the active Index referencing the item (including the given one).
+    pub fn ref_count(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.ref_count.load(Ordering::Relaxed)
+    }
+
+    /// Iterate over all the items including the pending requests.
+    pub fn iter(&self) -> Iter<'_, D> {
+        Iter {
+            active: self.shared.resources.iter(),
+            pending: Some(self.locked_exclusive.requests.iter()),
+        }
+    }
+
+    /// Iterate over all the items including the pending requests with mutable access to the data.
+    pub fn iter_mut(&mut self) -> IterMut<'_, D> {
+        IterMut {
+            active: self.shared.resources.iter(),
+            pending: self.locked_exclusive.requests.iter(),
+        }
+    }
+
+    /// Returns the number of active and pending items.
+    pub fn summary(&self) -> Summary {
+        Summary::collect(&self.shared.resources, Some(&self.locked_exclusive.requests))
+    }
+}
+
+impl<'a, 'i, D: 'a + Data> ops::Index<&'i Index<D>> for WriteGuard<'a, D> {
+    type Output = D;
+
+    fn index(&self, index: &Index<D>) -> &Self::Output {
+        self.at(index)
+    }
+}
+
+impl<'a, 'i, D: 'a + Data> ops::IndexMut<&'i Index<D>> for WriteGuard<'a, D> {
+    fn index_mut(&mut self, index: &Index<D>) -> &mut Self::Output {
+        self.at_mut(index)
+    }
+}
+
+/// Diagnostic information of a store
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    /// Number of the finalized items
+    pub active: usize,
+    /// Number of the items waiting for finalize_requests
+    pub pending: usize,
+    /// Number of the items without any reference
+    pub unreferenced: usize,
+    /// Number of the items either waiting for or being processed by the loader
+    pub loading: usize,
+}
+
+impl Summary {
+    fn collect<D: Data>(active: &HashMap<D::Key, *mut Entry<D>>, pending: Option<&HashMap<D::Key, *mut Entry<D>>>) -> Summary {
+        let mut summary = Summary {
+            active: active.len(),
+            pending: pending.map(|p| p.len()).unwrap_or(0),
+            unreferenced: 0,
+            loading: 0,
+        };
+        for &entry in active.values().chain(pending.into_iter().flat_map(|p| p.values())) {
+            let entry = unsafe { &*entry };
+            if entry.ref_count.load(Ordering::Relaxed) == 0 {
+                summary.unreferenced += 1;
+            }
+            if entry.state == LoadState::Pending || entry.state == LoadState::Loading {
+                summary.loading += 1;
+            }
+        }
+        summary
+    }
+}
+
+/// Iterator over the (key, index, &data) triplets of a store
+pub struct Iter<'a, D: Data> {
+    active: hash_map::Iter<'a, D::Key, *mut Entry<D>>,
+    pending: Option<hash_map::Iter<'a, D::Key, *mut Entry<D>>>,
+}
+
+impl<'a, D: 'a + Data> Iterator for Iter<'a, D> {
+    type Item = (&'a D::Key, Index<D>, &'a D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &mut self.pending;
+        let (k, &entry) = self.active.next().or_else(|| pending.as_mut().and_then(|p| p.next()))?;
+        Some((k, Index::new(entry), unsafe { &(*entry).value }))
+    }
+}
+
+/// Iterator over the (key, index, &mut data) triplets of a store
+pub struct IterMut<'a, D: Data> {
+    active: hash_map::Iter<'a, D::Key, *mut Entry<D>>,
+    pending: hash_map::Iter<'a, D::Key, *mut Entry<D>>,
+}
+
+impl<'a, D: 'a + Data> Iterator for IterMut<'a, D> {
+    type Item = (&'a D::Key, Index<D>, &'a mut D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &mut self.pending;
+        let (k, &entry) = self.active.next().or_else(|| pending.next())?;
+        Some((k, Index::new(entry), unsafe { &mut (*entry).value }))
+    }
+}
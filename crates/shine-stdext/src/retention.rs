@@ -0,0 +1,96 @@
+/// Information of an unreferenced item of a store considered for eviction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unused {
+    /// The last frame the item was referenced
+    pub last_used: usize,
+    /// Cost (ex. memory footprint) of the item as reported by the RetentionPolicy
+    pub cost: usize,
+}
+
+/// Policy to decide which of the unreferenced items shall be evicted from a store.
+pub trait RetentionPolicy<D>: Send {
+    /// Returns the cost of an item used for the budget calculation.
+    fn cost(&self, _data: &D) -> usize {
+        1
+    }
+
+    /// Returns the number of the items to evict.
+    /// The unused items are ordered by the last use, the least recently used item comes first
+    /// and the items are evicted from the front.
+    fn evict_count(&mut self, frame: usize, unused: &[Unused]) -> usize;
+}
+
+/// Evict all the unreferenced items immediately.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DropUnused;
+
+impl<D> RetentionPolicy<D> for DropUnused {
+    fn evict_count(&mut self, _frame: usize, unused: &[Unused]) -> usize {
+        unused.len()
+    }
+}
+
+/// Keep the unreferenced items alive up to a count and cost budget and evict the least recently used
+/// items first. Optionally items unreferenced for too many frames are also evicted.
+pub struct LruBudget<D> {
+    max_count: usize,
+    max_cost: usize,
+    max_age: usize,
+    cost: fn(&D) -> usize,
+}
+
+impl<D> LruBudget<D> {
+    /// Creates a policy that keeps at most max_count unreferenced items.
+    pub fn new(max_count: usize) -> LruBudget<D> {
+        LruBudget {
+            max_count,
+            max_cost: usize::max_value(),
+            max_age: usize::max_value(),
+            cost: |_| 1,
+        }
+    }
+
+    /// Limit the total cost of the unreferenced items. The cost of an item is given by the cost function.
+    pub fn with_cost_budget(self, max_cost: usize, cost: fn(&D) -> usize) -> LruBudget<D> {
+        LruBudget { max_cost, cost, ..self }
+    }
+
+    /// Evict the items unreferenced for more than max_age frames.
+    pub fn with_max_age(self, max_age: usize) -> LruBudget<D> {
+        LruBudget { max_age, ..self }
+    }
+}
+
+impl<D> RetentionPolicy<D> for LruBudget<D> {
+    fn cost(&self, data: &D) -> usize {
+        (self.cost)(data)
+    }
+
+    fn evict_count(&mut self, frame: usize, unused: &[Unused]) -> usize {
+        let mut count = unused.len();
+        let mut cost = unused.iter().fold(0usize, |sum, u| sum.saturating_add(u.cost));
+        let mut evicted = 0;
+        for u in unused {
+            let expired = frame.saturating_sub(u.last_used) > self.max_age;
+            if !expired && count <= self.max_count && cost <= self.max_cost {
+                break;
+            }
+            count -= 1;
+            cost = cost.saturating_sub(u.cost);
+            evicted += 1;
+        }
+        evicted
+    }
+}
+
+/// Sort the candidates by the last use and select the items to evict by the policy.
+pub(crate) fn select_evicted<D, T>(
+    policy: &mut dyn RetentionPolicy<D>,
+    frame: usize,
+    mut candidates: Vec<(Unused, T)>,
+) -> Vec<T> {
+    candidates.sort_by_key(|(u, _)| u.last_used);
+    let unused: Vec<_> = candidates.iter().map(|(u, _)| *u).collect();
+    let count = policy.evict_count(frame, &unused);
+    candidates.into_iter().take(count).map(|(_, t)| t).collect()
+}
@@ -0,0 +1,173 @@
+use crate::spscstate::AlignedData;
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Bounded ring buffer that uses atomic operations to synchronize a single producer and a single consumer.
+/// The read and write positions are increasing (wrapping) counters, the slot of a position is given by masking,
+/// thus the capacity is always a power of two.
+struct RingBuffer<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    mask: usize,
+
+    // position of the next item to read, updated by the consumer
+    head: AlignedData<AtomicUsize>,
+    // position of the next item to write, updated by the producer
+    tail: AlignedData<AtomicUsize>,
+}
+
+impl<T> RingBuffer<T> {
+    fn new(capacity: usize) -> RingBuffer<T> {
+        let capacity = capacity.max(1).next_power_of_two();
+        RingBuffer {
+            buffer: (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+            mask: capacity - 1,
+            head: AlignedData(AtomicUsize::new(0)),
+            tail: AlignedData(AtomicUsize::new(0)),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn len(&self) -> usize {
+        let tail = self.tail.0.load(Ordering::Acquire);
+        let head = self.head.0.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    /// Writes the item into the buffer. Shall be called only by the producer.
+    fn try_push(&self, value: T) -> Result<(), T> {
+        let tail = self.tail.0.load(Ordering::Relaxed);
+        let head = self.head.0.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == self.capacity() {
+            return Err(value);
+        }
+
+        unsafe { ptr::write((*self.buffer[tail & self.mask].get()).as_mut_ptr(), value) };
+        self.tail.0.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Reads the next item from the buffer. Shall be called only by the consumer.
+    fn try_pop(&self) -> Option<T> {
+        let head = self.head.0.load(Ordering::Relaxed);
+        let tail = self.tail.0.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        let value = unsafe { ptr::read((*self.buffer[head & self.mask].get()).as_ptr()) };
+        self.head.0.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+    }
+}
+
+/// Sender part of the queue.
+pub struct Sender<T>(Arc<RingBuffer<T>>);
+
+// The sender can be sent from place to place, so long as it
+// is not used to send non-sendable things.
+unsafe impl<T: Send> Send for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Try to add an item to the queue. If the queue is full, the item is returned back in the Error.
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        self.0.try_push(value)
+    }
+
+    /// Returns the maximum number of items in the queue.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of items in the queue. As the consumer runs concurrently, it is an upper bound.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+}
+
+/// Receiver part of the queue.
+pub struct Receiver<T>(Arc<RingBuffer<T>>);
+
+// The receiver can be sent from place to place, so long as it
+// is not used to receive non-sendable things.
+unsafe impl<T: Send> Send for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Try to get the next item from the queue. If the queue is empty, None is returned.
+    pub fn try_recv(&self) -> Option<T> {
+        self.0.try_pop()
+    }
+
+    /// Returns an iterator to receive the available items in a batch.
+    /// Only the items present at the time of the call are returned,
+    /// the ones sent during the iteration are left in the queue.
+    pub fn drain(&self) -> Drain<'_, T> {
+        Drain {
+            receiver: self,
+            remaining: self.len(),
+        }
+    }
+
+    /// Returns the maximum number of items in the queue.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of items in the queue. As the producer runs concurrently, it is a lower bound.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Iterator receiving a batch of items
+pub struct Drain<'a, T> {
+    receiver: &'a Receiver<T>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.receiver.try_recv()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Create a Sender/Receiver pair with a bounded buffer for lossless communication.
+/// The capacity is rounded up to the next power of two.
+/// Contrary to the state_channel, no message is dropped, if the queue is full, sending fails.
+pub fn queue_channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let a = Arc::new(RingBuffer::new(capacity));
+    (Sender(a.clone()), Receiver(a))
+}
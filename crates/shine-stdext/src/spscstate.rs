@@ -8,7 +8,7 @@ type AtomicFlag = AtomicUsize;
 /// Struct to force the alignment of the stored data match the typical size of a cache-line
 /// to avoid false sharing.
 #[repr(align(64))]
-struct AlignedData<T>(T);
+pub(crate) struct AlignedData<T>(pub T);
 
 /// Triple buffer that uses atomic operations to rotate the 3 buffers during consume/produce operations
 struct TripleBuffer<T> {
@@ -195,3 +195,233 @@ pub fn state_channel<T: Default>() -> (Sender<T>, Receiver<T>) {
     let a = Arc::new(TripleBuffer::new());
     (Sender::new(&a), Receiver::new(&a))
 }
+
+/// Sentinel for consumers not holding any buffer
+const NO_BUFFER: usize = usize::max_value();
+
+/// A published value with its sequence number
+struct SequencedData<T> {
+    value: T,
+    sequence: usize,
+}
+
+/// Buffer for a single producer and a fixed number of consumers. Each consumer holds at most one buffer, one
+/// buffer is the latest published and one is written by the producer, thus consumer_count+2 buffers are
+/// always enough for the producer to find a free buffer without blocking.
+struct MultiBuffer<T> {
+    buffers: Vec<AlignedData<UnsafeCell<SequencedData<T>>>>,
+
+    // index of the latest published buffer
+    latest: AlignedData<AtomicUsize>,
+
+    // index of the buffer held by each consumer (or NO_BUFFER)
+    held: Vec<AlignedData<AtomicUsize>>,
+}
+
+unsafe impl<T> Sync for MultiBuffer<T> {}
+
+impl<T: Default> MultiBuffer<T> {
+    pub fn new(consumer_count: usize) -> MultiBuffer<T> {
+        MultiBuffer {
+            buffers: (0..consumer_count + 2)
+                .map(|_| {
+                    AlignedData(UnsafeCell::new(SequencedData {
+                        value: Default::default(),
+                        sequence: 0,
+                    }))
+                })
+                .collect(),
+            latest: AlignedData(AtomicUsize::new(0)),
+            held: (0..consumer_count)
+                .map(|_| AlignedData(AtomicUsize::new(NO_BUFFER)))
+                .collect(),
+        }
+    }
+}
+
+impl<T> MultiBuffer<T> {
+    /// Finds a buffer that is neither the latest nor held by any consumer.
+    /// As the producer is the only one to publish new buffers, the result remains valid until the next publish.
+    fn get_produce_index(&self) -> usize {
+        let latest = self.latest.0.load(Ordering::SeqCst);
+        (0..self.buffers.len())
+            .find(|&idx| idx != latest && self.held.iter().all(|h| h.0.load(Ordering::SeqCst) != idx))
+            .expect("No free buffer found")
+    }
+
+    /// Publishes a produced buffer with the given sequence number.
+    fn set_produce(&self, idx: usize, sequence: usize) {
+        unsafe { (*self.buffers[idx].0.get()).sequence = sequence };
+        self.latest.0.store(idx, Ordering::SeqCst);
+    }
+
+    /// Marks the latest buffer as held by the consumer and returns its index.
+    fn acquire_latest(&self, consumer: usize) -> usize {
+        let held = &self.held[consumer].0;
+        let mut latest = self.latest.0.load(Ordering::SeqCst);
+        loop {
+            held.store(latest, Ordering::SeqCst);
+            // if the latest has not changed during the hold, producer won't pick it for write
+            let current = self.latest.0.load(Ordering::SeqCst);
+            if current == latest {
+                return latest;
+            }
+            latest = current;
+        }
+    }
+
+    fn get(&self, idx: usize) -> &SequencedData<T> {
+        unsafe { &*self.buffers[idx].0.get() }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn get_mut(&self, idx: usize) -> &mut SequencedData<T> {
+        unsafe { &mut *self.buffers[idx].0.get() }
+    }
+}
+
+/// Sender part of the single producer multi consumer communication.
+pub struct MultiSender<T> {
+    buffer: Arc<MultiBuffer<T>>,
+    sequence: usize,
+}
+
+unsafe impl<T: Send> Send for MultiSender<T> {}
+
+impl<T> MultiSender<T> {
+    pub fn send_buffer(&mut self) -> Result<RefMultiSendBuffer<'_, T>, ()> {
+        let idx = self.buffer.get_produce_index();
+        Ok(RefMultiSendBuffer(self, idx))
+    }
+
+    /// Returns the sequence number of the last published buffer
+    pub fn sequence(&self) -> usize {
+        self.sequence
+    }
+}
+
+impl<T: Copy> MultiSender<T> {
+    pub fn send(&mut self, value: T) -> Result<(), ()> {
+        match self.send_buffer() {
+            Ok(mut b) => {
+                *b = value;
+                Ok(())
+            }
+            Err(_) => Err(()),
+        }
+    }
+}
+
+/// Reference to the buffer held by the producer, the buffer is published on drop.
+pub struct RefMultiSendBuffer<'a, T>(&'a mut MultiSender<T>, usize);
+
+impl<'a, T> Drop for RefMultiSendBuffer<'a, T> {
+    fn drop(&mut self) {
+        self.0.sequence += 1;
+        self.0.buffer.set_produce(self.1, self.0.sequence);
+    }
+}
+
+impl<'a, T> Deref for RefMultiSendBuffer<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0.buffer.get(self.1).value
+    }
+}
+
+impl<'a, T> DerefMut for RefMultiSendBuffer<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0.buffer.get_mut(self.1).value
+    }
+}
+
+/// Receiver part of the single producer multi consumer communication.
+pub struct MultiReceiver<T> {
+    buffer: Arc<MultiBuffer<T>>,
+    id: usize,
+    sequence: usize,
+}
+
+// Consumers may read the same buffer at the same time, thus data have to be Sync
+unsafe impl<T: Send + Sync> Send for MultiReceiver<T> {}
+
+impl<T> MultiReceiver<T> {
+    /// Acquires the latest published buffer. If nothing new was published since the last receive,
+    /// Err is returned.
+    pub fn receive_buffer(&mut self) -> Result<RefMultiReceiveBuffer<'_, T>, ()> {
+        let idx = self.buffer.acquire_latest(self.id);
+        let sequence = self.buffer.get(idx).sequence;
+        if sequence == self.sequence {
+            return Err(());
+        }
+        let skipped = sequence - self.sequence - 1;
+        self.sequence = sequence;
+        Ok(RefMultiReceiveBuffer {
+            buffer: &self.buffer,
+            idx,
+            skipped,
+        })
+    }
+
+    /// Returns the sequence number of the last received buffer
+    pub fn sequence(&self) -> usize {
+        self.sequence
+    }
+}
+
+impl<T: Copy> MultiReceiver<T> {
+    pub fn receive(&mut self) -> Result<T, ()> {
+        match self.receive_buffer() {
+            Ok(b) => Ok(*b),
+            Err(_) => Err(()),
+        }
+    }
+}
+
+impl<T> Drop for MultiReceiver<T> {
+    fn drop(&mut self) {
+        self.buffer.held[self.id].0.store(NO_BUFFER, Ordering::SeqCst);
+    }
+}
+
+/// Reference to the buffer held by a consumer
+pub struct RefMultiReceiveBuffer<'a, T> {
+    buffer: &'a MultiBuffer<T>,
+    idx: usize,
+    skipped: usize,
+}
+
+impl<'a, T> RefMultiReceiveBuffer<'a, T> {
+    /// Returns the sequence number of the buffer
+    pub fn sequence(&self) -> usize {
+        self.buffer.get(self.idx).sequence
+    }
+
+    /// Returns the number of published buffers missed by the consumer since the previous receive
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a, T> Deref for RefMultiReceiveBuffer<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.buffer.get(self.idx).value
+    }
+}
+
+/// Create a Sender with consumer_count Receivers sharing a buffer for communication.
+/// Similar to the state_channel, each consumer sees the latest published state without blocking the producer
+/// and some massages might be dropped depending on the thread scheduling. The buffers are tagged by a sequence
+/// number, thus the consumers can detect the skipped messages.
+pub fn multi_state_channel<T: Default>(consumer_count: usize) -> (MultiSender<T>, Vec<MultiReceiver<T>>) {
+    let a = Arc::new(MultiBuffer::new(consumer_count));
+    let receivers = (0..consumer_count)
+        .map(|id| MultiReceiver {
+            buffer: a.clone(),
+            id,
+            sequence: 0,
+        })
+        .collect();
+    (MultiSender { buffer: a, sequence: 0 }, receivers)
+}
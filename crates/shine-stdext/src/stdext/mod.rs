@@ -1,3 +1,5 @@
 mod ordslice;
+mod sortediter;
 
 pub use self::ordslice::*;
+pub use self::sortediter::*;
@@ -1,14 +1,44 @@
+use crate::stdext::{SortedDifference, SortedIntersection, SortedUnion};
 use std::cmp::Ordering;
+use std::ops::Range;
 
 /// Extension trait for slices of #Ord items
 pub trait SliceOrdExt {
     type Item: Ord;
 
+    /// Return the index of the first item not less than x.
     fn lower_bound(&self, x: &Self::Item) -> usize;
 
     fn lower_bound_by<'a, F>(&'a self, f: F) -> usize
     where
         F: FnMut(&'a Self::Item) -> Ordering;
+
+    /// Return the index of the first item greater than x.
+    fn upper_bound(&self, x: &Self::Item) -> usize;
+
+    fn upper_bound_by<'a, F>(&'a self, f: F) -> usize
+    where
+        F: FnMut(&'a Self::Item) -> Ordering;
+
+    /// Return the range of the items equal to x.
+    fn equal_range(&self, x: &Self::Item) -> Range<usize>;
+
+    /// Same as lower_bound, but the search is performed by exponential steps starting at the hint position.
+    /// It is faster than the binary search if the result is close to the hint.
+    fn gallop_lower_bound(&self, hint: usize, x: &Self::Item) -> usize;
+
+    fn gallop_lower_bound_by<'a, F>(&'a self, hint: usize, f: F) -> usize
+    where
+        F: FnMut(&'a Self::Item) -> Ordering;
+
+    /// Return an iterator over the union of two sorted sets.
+    fn sorted_union<'a>(&'a self, other: &'a [Self::Item]) -> SortedUnion<'a, Self::Item>;
+
+    /// Return an iterator over the intersection of two sorted sets.
+    fn sorted_intersection<'a>(&'a self, other: &'a [Self::Item]) -> SortedIntersection<'a, Self::Item>;
+
+    /// Return an iterator over the items of self not present in the other sorted set.
+    fn sorted_difference<'a>(&'a self, other: &'a [Self::Item]) -> SortedDifference<'a, Self::Item>;
 }
 
 impl<T: Ord> SliceOrdExt for [T] {
@@ -37,4 +67,99 @@ impl<T: Ord> SliceOrdExt for [T] {
         let cmp = f(unsafe { self.get_unchecked(base) });
         base + (cmp == Ordering::Less) as usize
     }
+
+    fn upper_bound(&self, x: &T) -> usize {
+        self.upper_bound_by(|y| y.cmp(x))
+    }
+
+    fn upper_bound_by<'a, F>(&'a self, mut f: F) -> usize
+    where
+        F: FnMut(&'a Self::Item) -> Ordering,
+    {
+        self.lower_bound_by(|y| match f(y) {
+            Ordering::Greater => Ordering::Greater,
+            _ => Ordering::Less,
+        })
+    }
+
+    fn equal_range(&self, x: &T) -> Range<usize> {
+        let start = self.lower_bound(x);
+        let end = self[start..].upper_bound(x) + start;
+        start..end
+    }
+
+    fn gallop_lower_bound(&self, hint: usize, x: &T) -> usize {
+        self.gallop_lower_bound_by(hint, |y| y.cmp(x))
+    }
+
+    fn gallop_lower_bound_by<'a, F>(&'a self, hint: usize, mut f: F) -> usize
+    where
+        F: FnMut(&'a Self::Item) -> Ordering,
+    {
+        let len = self.len();
+        let hint = hint.min(len);
+
+        if hint < len && f(&self[hint]) == Ordering::Less {
+            // search forward, invariant: self[lo] < x
+            let mut lo = hint;
+            let mut step = 1;
+            loop {
+                let hi = lo + step;
+                if hi >= len {
+                    return lo + 1 + self[lo + 1..].lower_bound_by(f);
+                }
+                if f(&self[hi]) != Ordering::Less {
+                    return lo + 1 + self[lo + 1..hi].lower_bound_by(f);
+                }
+                lo = hi;
+                step *= 2;
+            }
+        } else {
+            // search backward, invariant: x <= self[hi] (or hi == len)
+            let mut hi = hint;
+            let mut step = 1;
+            loop {
+                if hi == 0 {
+                    return 0;
+                }
+                let lo = hi.saturating_sub(step);
+                if f(&self[lo]) == Ordering::Less {
+                    return lo + 1 + self[lo + 1..hi].lower_bound_by(f);
+                }
+                hi = lo;
+                step *= 2;
+            }
+        }
+    }
+
+    fn sorted_union<'a>(&'a self, other: &'a [T]) -> SortedUnion<'a, T> {
+        SortedUnion::new(self, other)
+    }
+
+    fn sorted_intersection<'a>(&'a self, other: &'a [T]) -> SortedIntersection<'a, T> {
+        SortedIntersection::new(self, other)
+    }
+
+    fn sorted_difference<'a>(&'a self, other: &'a [T]) -> SortedDifference<'a, T> {
+        SortedDifference::new(self, other)
+    }
+}
+
+/// Extension trait for vectors of #Ord items
+pub trait VecOrdExt {
+    type Item: Ord;
+
+    /// Insert an item into a sorted vector keeping the order and return its index.
+    /// Equal items are kept in the order of insertion.
+    fn sorted_insert(&mut self, x: Self::Item) -> usize;
+}
+
+impl<T: Ord> VecOrdExt for Vec<T> {
+    type Item = T;
+
+    fn sorted_insert(&mut self, x: T) -> usize {
+        let pos = self.upper_bound(&x);
+        self.insert(pos, x);
+        pos
+    }
 }
@@ -0,0 +1,129 @@
+use crate::stdext::SliceOrdExt;
+use std::cmp::Ordering;
+
+/// Iterator over the union of two sorted sets.
+/// If an item is present in both sets, the one from the first set is returned.
+pub struct SortedUnion<'a, T: Ord> {
+    a: &'a [T],
+    b: &'a [T],
+}
+
+impl<'a, T: Ord> SortedUnion<'a, T> {
+    pub fn new(a: &'a [T], b: &'a [T]) -> SortedUnion<'a, T> {
+        SortedUnion { a, b }
+    }
+}
+
+impl<'a, T: Ord> Iterator for SortedUnion<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (a, b) = (self.a, self.b);
+        match (a.first(), b.first()) {
+            (Some(x), Some(y)) => match x.cmp(y) {
+                Ordering::Less => {
+                    self.a = &a[1..];
+                    Some(x)
+                }
+                Ordering::Greater => {
+                    self.b = &b[1..];
+                    Some(y)
+                }
+                Ordering::Equal => {
+                    self.a = &a[1..];
+                    self.b = &b[1..];
+                    Some(x)
+                }
+            },
+            (Some(x), None) => {
+                self.a = &a[1..];
+                Some(x)
+            }
+            (None, Some(y)) => {
+                self.b = &b[1..];
+                Some(y)
+            }
+            (None, None) => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = (self.a.len(), self.b.len());
+        (a.max(b), Some(a + b))
+    }
+}
+
+/// Iterator over the intersection of two sorted sets.
+/// Items not in the intersection are skipped by galloping search.
+pub struct SortedIntersection<'a, T: Ord> {
+    a: &'a [T],
+    b: &'a [T],
+}
+
+impl<'a, T: Ord> SortedIntersection<'a, T> {
+    pub fn new(a: &'a [T], b: &'a [T]) -> SortedIntersection<'a, T> {
+        SortedIntersection { a, b }
+    }
+}
+
+impl<'a, T: Ord> Iterator for SortedIntersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let (a, b) = (self.a, self.b);
+            let (x, y) = match (a.first(), b.first()) {
+                (Some(x), Some(y)) => (x, y),
+                _ => return None,
+            };
+            match x.cmp(y) {
+                Ordering::Less => self.a = &a[a.gallop_lower_bound(0, y)..],
+                Ordering::Greater => self.b = &b[b.gallop_lower_bound(0, x)..],
+                Ordering::Equal => {
+                    self.a = &a[1..];
+                    self.b = &b[1..];
+                    return Some(x);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.a.len().min(self.b.len())))
+    }
+}
+
+/// Iterator over the items of the first sorted set not present in the second one.
+pub struct SortedDifference<'a, T: Ord> {
+    a: &'a [T],
+    b: &'a [T],
+}
+
+impl<'a, T: Ord> SortedDifference<'a, T> {
+    pub fn new(a: &'a [T], b: &'a [T]) -> SortedDifference<'a, T> {
+        SortedDifference { a, b }
+    }
+}
+
+impl<'a, T: Ord> Iterator for SortedDifference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let a = self.a;
+            let x = a.first()?;
+            if !self.b.is_empty() {
+                self.b = &self.b[self.b.gallop_lower_bound(0, x)..];
+            }
+            self.a = &a[1..];
+            match self.b.first() {
+                Some(y) if y == x => continue,
+                _ => return Some(x),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.a.len()))
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source of the (monotonic) time used by the timers.
+pub trait Clock {
+    /// Return the current time.
+    fn now(&self) -> Instant;
+
+    /// Block the current thread for the given duration.
+    fn sleep(&self, duration: Duration);
+
+    /// Perform a single iteration of a busy wait.
+    fn spin(&self) {
+        thread::yield_now();
+    }
+
+    /// Return the time elapsed since the given time.
+    fn elapsed(&self, since: Instant) -> Duration {
+        self.now().duration_since(since)
+    }
+}
+
+impl<C: Clock> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+
+    fn spin(&self) {
+        (**self).spin()
+    }
+}
+
+/// The real monotonic clock of the system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+struct ManualClockState {
+    origin: Instant,
+    elapsed_ns: AtomicU64,
+    spin_step_ns: AtomicU64,
+    sleep_overshoot_ns: AtomicU64,
+}
+
+/// Virtual clock advanced manually, mainly for testing.
+/// The clones share the same time, thus the clock can be advanced while a timer owns a clone of it.
+/// Sleeping advances the time immediately (with an optional overshoot) and each spin iteration advances it by the spin step.
+#[derive(Clone)]
+pub struct ManualClock(Arc<ManualClockState>);
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock(Arc::new(ManualClockState {
+            origin: Instant::now(),
+            elapsed_ns: AtomicU64::new(0),
+            spin_step_ns: AtomicU64::new(1_000),
+            sleep_overshoot_ns: AtomicU64::new(0),
+        }))
+    }
+
+    /// Set the time advanced by a single spin iteration
+    pub fn set_spin_step(&self, step: Duration) {
+        self.0.spin_step_ns.store(step.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Set the extra time added to each sleep to emulate the inaccuracy of the system
+    pub fn set_sleep_overshoot(&self, overshoot: Duration) {
+        self.0
+            .sleep_overshoot_ns
+            .store(overshoot.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Advance the time of the clock
+    pub fn advance(&self, duration: Duration) {
+        self.0.elapsed_ns.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Return the total time advanced since the creation of the clock
+    pub fn total_elapsed(&self) -> Duration {
+        Duration::from_nanos(self.0.elapsed_ns.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.0.origin + self.total_elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let overshoot = self.0.sleep_overshoot_ns.load(Ordering::Relaxed);
+        self.advance(duration + Duration::from_nanos(overshoot));
+    }
+
+    fn spin(&self) {
+        let step = self.0.spin_step_ns.load(Ordering::Relaxed);
+        self.0.elapsed_ns.fetch_add(step, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ManualClock({:?})", self.total_elapsed())
+    }
+}
+
+struct ScaledClockState {
+    scale: f64,
+    // time of the source and the scaled clock when the scale was last modified
+    source_origin: Instant,
+    origin: Instant,
+}
+
+/// Clock running faster or slower than the source clock.
+/// The clones share the same scale.
+#[derive(Clone)]
+pub struct ScaledClock<C: Clock = RealClock> {
+    source: C,
+    state: Arc<Mutex<ScaledClockState>>,
+}
+
+impl<C: Clock> ScaledClock<C> {
+    pub fn new(source: C, scale: f64) -> ScaledClock<C> {
+        assert!(scale > 0.);
+        let now = source.now();
+        ScaledClock {
+            source,
+            state: Arc::new(Mutex::new(ScaledClockState {
+                scale,
+                source_origin: now,
+                origin: now,
+            })),
+        }
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.state.lock().unwrap().scale
+    }
+
+    /// Modify the scale, the time already passed is not affected.
+    pub fn set_scale(&self, scale: f64) {
+        assert!(scale > 0.);
+        let mut state = self.state.lock().unwrap();
+        let source_now = self.source.now();
+        state.origin = Self::scaled_now(&state, source_now);
+        state.source_origin = source_now;
+        state.scale = scale;
+    }
+
+    pub fn source(&self) -> &C {
+        &self.source
+    }
+
+    fn scaled_now(state: &ScaledClockState, source_now: Instant) -> Instant {
+        let elapsed = source_now.duration_since(state.source_origin);
+        let scaled = elapsed.as_nanos() as f64 * state.scale;
+        state.origin + Duration::from_nanos(scaled as u64)
+    }
+}
+
+impl<C: Clock> Clock for ScaledClock<C> {
+    fn now(&self) -> Instant {
+        let state = self.state.lock().unwrap();
+        Self::scaled_now(&state, self.source.now())
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let scaled = duration.as_nanos() as f64 / self.scale();
+        self.source.sleep(Duration::from_nanos(scaled as u64));
+    }
+
+    fn spin(&self) {
+        self.source.spin();
+    }
+}
+
+impl<C: Clock> fmt::Debug for ScaledClock<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ScaledClock({})", self.scale())
+    }
+}
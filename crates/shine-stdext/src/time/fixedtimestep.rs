@@ -0,0 +1,170 @@
+use crate::time::{BlendScale, Clock, RealClock, ScaledBlender};
+use std::time::{Duration, Instant};
+
+/// Default limit of the steps in a single frame
+pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+/// Deterministic fixed timestep clock.
+/// The (scaled) elapsed real time is accumulated and consumed in fixed length steps. The remaining time
+/// gives the interpolation alpha between the last two logic states.
+#[derive(Debug)]
+pub struct FixedTimestep<C: Clock = RealClock> {
+    clock: C,
+    step: Duration,
+    max_steps: u32,
+    time_scale: f32,
+    paused: bool,
+    last_update: Option<Instant>,
+    accumulator: Duration,
+    step_count: u64,
+    dropped_time: Duration,
+}
+
+impl FixedTimestep<RealClock> {
+    pub fn new(step: Duration) -> FixedTimestep<RealClock> {
+        FixedTimestep::with_clock(RealClock, step)
+    }
+}
+
+impl<C: Clock> FixedTimestep<C> {
+    pub fn with_clock(clock: C, step: Duration) -> FixedTimestep<C> {
+        assert!(step > Duration::default());
+        FixedTimestep {
+            clock,
+            step,
+            max_steps: DEFAULT_MAX_STEPS,
+            time_scale: 1.,
+            paused: false,
+            last_update: None,
+            accumulator: Duration::default(),
+            step_count: 0,
+            dropped_time: Duration::default(),
+        }
+    }
+
+    /// Set the maximum number of steps in a single frame to avoid the spiral of death.
+    /// The time that would require more steps is dropped.
+    pub fn with_max_steps(self, max_steps: u32) -> FixedTimestep<C> {
+        assert!(max_steps > 0);
+        FixedTimestep { max_steps, ..self }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    pub fn step_s(&self) -> f32 {
+        self.step.as_micros() as f32 / 1_000_000.
+    }
+
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Set the speed of the simulation compared to the real time.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        assert!(time_scale >= 0.);
+        self.time_scale = time_scale;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause the simulation, the elapsed time is not accumulated until resumed.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Return the total number of steps performed.
+    pub fn step_count(&self) -> u64 {
+        self.step_count
+    }
+
+    /// Return the total simulated time.
+    pub fn simulated_time(&self) -> Duration {
+        Duration::from_nanos((self.step.as_nanos() * u128::from(self.step_count)) as u64)
+    }
+
+    /// Return the total time dropped due to the step limit.
+    pub fn dropped_time(&self) -> Duration {
+        self.dropped_time
+    }
+
+    /// Forget the accumulated time and restart the measurement from now.
+    pub fn reset(&mut self) {
+        self.last_update = None;
+        self.accumulator = Duration::default();
+    }
+
+    /// Accumulate the time of the clock elapsed since the previous update and
+    /// return the number of steps to perform in this frame.
+    /// The first call only starts the time measurement and returns 0.
+    pub fn update(&mut self) -> u32 {
+        let now = self.clock.now();
+        match self.last_update.replace(now) {
+            Some(last) => self.advance(now.duration_since(last)),
+            None => 0,
+        }
+    }
+
+    /// Accumulate the given (real) time and return the number of steps to perform in this frame.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        if !self.paused {
+            let scaled = elapsed.as_nanos() as f64 * f64::from(self.time_scale);
+            self.accumulator += Duration::from_nanos(scaled as u64);
+        }
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if steps == self.max_steps {
+                // drop the time exceeding the limit, but keep the fraction of the step for the blending
+                let dropped = self.accumulator - self.accumulator_fraction();
+                self.dropped_time += dropped;
+                self.accumulator -= dropped;
+                break;
+            }
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        self.step_count += u64::from(steps);
+        steps
+    }
+
+    fn accumulator_fraction(&self) -> Duration {
+        let step = self.step.as_nanos();
+        Duration::from_nanos((self.accumulator.as_nanos() % step) as u64)
+    }
+
+    /// Return the accumulated time not consumed by the steps.
+    pub fn accumulated(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// Return the interpolation weight in the [0,1) range between the last two steps.
+    pub fn alpha(&self) -> f32 {
+        let alpha = (self.accumulator.as_nanos() as f64 / self.step.as_nanos() as f64) as f32;
+        alpha.min(1.)
+    }
+
+    pub fn get_blender(&self) -> ScaledBlender<()> {
+        ScaledBlender::new(0., self.alpha(), ())
+    }
+
+    pub fn get_scaled_blender<F: BlendScale>(&self, scale: F) -> ScaledBlender<F> {
+        ScaledBlender::new(0., self.alpha(), scale)
+    }
+}
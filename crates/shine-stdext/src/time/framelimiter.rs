@@ -1,34 +1,86 @@
+use crate::time::{Clock, RealClock};
 use std::fmt;
-use std::thread;
 use std::time::{Duration, Instant};
 
+/// Default time left for spinning when the sleep/spin boundary is not adaptive
+pub const DEFAULT_SLEEP_LIMIT: Duration = Duration::from_millis(2);
+/// Lower bound of the calibrated sleep limit
+pub const MIN_SLEEP_LIMIT: Duration = Duration::from_micros(50);
+/// Upper bound of the calibrated sleep limit
+pub const MAX_SLEEP_LIMIT: Duration = Duration::from_millis(10);
+
 /// Limit policy used by the FrameLimiter
 pub enum FrameLimit {
     Sleep(Duration),
     Spin(Duration),
     SleepSpin(Duration),
+    /// Sleep and spin until the next deadline of a fixed period. The deadline is computed from the previous
+    /// deadline to avoid drifting. If the frame is late by more than a period, the missed deadlines are skipped.
+    Period(Duration),
 }
 
 /// Limit frame rate by sleeping and spinning.
-pub struct FrameLimiter {
+pub struct FrameLimiter<C: Clock = RealClock> {
+    clock: C,
     start: Option<Instant>,
+    deadline: Option<Instant>,
     sleep_limit: Duration,
+    adaptive: bool,
+    last_overshoot: Duration,
     work_time: Duration,
     sleep_time: Duration,
     spin_time: Duration,
 }
 
-impl FrameLimiter {
-    pub fn new() -> FrameLimiter {
+impl FrameLimiter<RealClock> {
+    pub fn new() -> FrameLimiter<RealClock> {
+        FrameLimiter::with_clock(RealClock)
+    }
+}
+
+impl<C: Clock> FrameLimiter<C> {
+    pub fn with_clock(clock: C) -> FrameLimiter<C> {
         FrameLimiter {
+            clock,
             start: None,
-            sleep_limit: Duration::from_millis(2),
+            deadline: None,
+            sleep_limit: DEFAULT_SLEEP_LIMIT,
+            adaptive: false,
+            last_overshoot: Duration::default(),
             work_time: Duration::default(),
             sleep_time: Duration::default(),
             spin_time: Duration::default(),
         }
     }
 
+    /// Set the time left for spinning at the end of the frame.
+    pub fn with_sleep_limit(self, sleep_limit: Duration) -> FrameLimiter<C> {
+        FrameLimiter { sleep_limit, ..self }
+    }
+
+    /// Calibrate the sleep limit from the measured oversleeping of the running machine.
+    pub fn with_adaptive_sleep_limit(self) -> FrameLimiter<C> {
+        FrameLimiter { adaptive: true, ..self }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn is_adaptive(&self) -> bool {
+        self.adaptive
+    }
+
+    /// Return the time left for spinning at the end of the frame. In adaptive mode it is the learned value.
+    pub fn sleep_limit(&self) -> Duration {
+        self.sleep_limit
+    }
+
+    /// Return how much the last sleep took longer than requested.
+    pub fn last_overshoot(&self) -> Duration {
+        self.last_overshoot
+    }
+
     pub fn work_time(&self) -> Duration {
         self.work_time
     }
@@ -42,70 +94,102 @@ impl FrameLimiter {
     }
 
     pub fn start(&mut self) {
-        self.start = Some(Instant::now());
+        self.start = Some(self.clock.now());
     }
 
+    /// Wait according to the limit policy and return the difference between the end of the frame
+    /// and the requested deadline in microseconds.
     pub fn limit(&mut self, limit: FrameLimit) -> i64 {
         let start = self.start.take().unwrap();
-        let elapsed = start.elapsed();
+        let elapsed = self.clock.elapsed(start);
         self.work_time += elapsed;
 
         match limit {
             FrameLimit::Sleep(limit) => {
-                self.do_sleep(start, limit);
-                Self::get_off_time(start, limit)
+                let deadline = start + limit;
+                self.do_sleep(deadline);
+                self.get_off_time(deadline)
             }
             FrameLimit::Spin(limit) => {
-                self.do_spin(start, limit);
-                Self::get_off_time(start, limit)
+                let deadline = start + limit;
+                self.do_spin(deadline);
+                self.get_off_time(deadline)
             }
             FrameLimit::SleepSpin(limit) => {
-                self.do_sleep(start, limit);
-                self.do_spin(start, limit);
-                Self::get_off_time(start, limit)
+                let deadline = start + limit;
+                self.do_sleep(deadline);
+                self.do_spin(deadline);
+                self.get_off_time(deadline)
+            }
+            FrameLimit::Period(period) => {
+                let now = self.clock.now();
+                let mut deadline = self.deadline.map(|d| d + period).unwrap_or(start + period);
+                if now >= deadline + period {
+                    deadline = now;
+                }
+                self.deadline = Some(deadline);
+                self.do_sleep(deadline);
+                self.do_spin(deadline);
+                self.get_off_time(deadline)
             }
         }
     }
 
-    fn get_off_time(start: Instant, limit: Duration) -> i64 {
-        let elapsed_us = start.elapsed().as_micros() as i64;
-        let limit_us = limit.as_micros() as i64;
-        elapsed_us - limit_us
+    fn get_off_time(&self, deadline: Instant) -> i64 {
+        let now = self.clock.now();
+        if now >= deadline {
+            now.duration_since(deadline).as_micros() as i64
+        } else {
+            -(deadline.duration_since(now).as_micros() as i64)
+        }
     }
 
-    fn do_sleep(&mut self, start: Instant, limit: Duration) {
-        let elapsed = start.elapsed();
-        if limit <= self.sleep_limit + elapsed {
+    fn do_sleep(&mut self, deadline: Instant) {
+        let now = self.clock.now();
+        if deadline <= now + self.sleep_limit {
             return;
         }
 
-        let wait = limit - elapsed - self.sleep_limit;
-        let sleep_start = Instant::now();
-        thread::sleep(wait);
-        self.sleep_time += sleep_start.elapsed();
+        let wait = deadline.duration_since(now) - self.sleep_limit;
+        self.clock.sleep(wait);
+        let slept = self.clock.elapsed(now);
+        self.sleep_time += slept;
+        self.last_overshoot = if slept > wait { slept - wait } else { Duration::default() };
+        if self.adaptive {
+            self.calibrate();
+        }
     }
 
-    fn do_spin(&mut self, start: Instant, limit: Duration) {
-        let spin_start = Instant::now();
-        loop {
-            let elapsed = start.elapsed();
-            if limit <= elapsed {
-                break;
-            }
-            thread::yield_now();
+    /// Move the sleep limit towards the measured overshoot (with some margin). Increase is applied immediately
+    /// to avoid missing the next deadlines, decrease is smoothed.
+    fn calibrate(&mut self) {
+        let target = self.last_overshoot + self.last_overshoot / 4;
+        let limit = if target > self.sleep_limit {
+            target
+        } else {
+            (self.sleep_limit * 7 + target) / 8
+        };
+        self.sleep_limit = limit.max(MIN_SLEEP_LIMIT).min(MAX_SLEEP_LIMIT);
+    }
+
+    fn do_spin(&mut self, deadline: Instant) {
+        let spin_start = self.clock.now();
+        while self.clock.now() < deadline {
+            self.clock.spin();
         }
-        self.spin_time += spin_start.elapsed();
+        self.spin_time += self.clock.elapsed(spin_start);
     }
 }
 
-impl fmt::Debug for FrameLimiter {
+impl<C: Clock> fmt::Debug for FrameLimiter<C> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "FrameLimit(Work({:?}), Sleep({:?}), Spin({:?}))",
+            "FrameLimit(Work({:?}), Sleep({:?}), Spin({:?}), SleepLimit({:?}))",
             self.work_time(),
             self.sleep_time(),
             self.spin_time(),
+            self.sleep_limit(),
         )
     }
 }
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// Default multiplier of the target frame length above which a frame is considered to be a spike
+pub const DEFAULT_SPIKE_FACTOR: f32 = 1.5;
+
+/// Fixed-bucket histogram of the frame lengths.
+/// The last bucket collects all the frames longer than the covered range.
+#[derive(Clone, Debug)]
+pub struct FrameHistogram {
+    bucket_width: Duration,
+    counts: Vec<usize>,
+}
+
+impl FrameHistogram {
+    pub fn new(bucket_width: Duration, bucket_count: usize) -> FrameHistogram {
+        assert!(bucket_width > Duration::default());
+        assert!(bucket_count > 0);
+        FrameHistogram {
+            bucket_width,
+            counts: vec![0; bucket_count],
+        }
+    }
+
+    pub fn bucket_width(&self) -> Duration {
+        self.bucket_width
+    }
+
+    /// Return the number of frames in each bucket.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Return the index of the bucket of a frame length.
+    pub fn bucket_of(&self, frame: Duration) -> usize {
+        let bucket = (frame.as_nanos() / self.bucket_width.as_nanos()) as usize;
+        bucket.min(self.counts.len() - 1)
+    }
+
+    fn add(&mut self, frame: Duration) {
+        let bucket = self.bucket_of(frame);
+        self.counts[bucket] += 1;
+    }
+
+    fn remove(&mut self, frame: Duration) {
+        let bucket = self.bucket_of(frame);
+        self.counts[bucket] -= 1;
+    }
+}
+
+/// Statistics of the frames in the window.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameSummary {
+    pub count: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub spikes: usize,
+}
+
+impl FrameSummary {
+    /// Return a compact json representation, durations are given in microseconds.
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"count":{},"min":{},"max":{},"mean":{},"p50":{},"p95":{},"p99":{},"spikes":{}}}"#,
+            self.count,
+            self.min.as_micros(),
+            self.max.as_micros(),
+            self.mean.as_micros(),
+            self.p50.as_micros(),
+            self.p95.as_micros(),
+            self.p99.as_micros(),
+            self.spikes
+        )
+    }
+}
+
+impl fmt::Display for FrameSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "frames: {}, min: {:?}, max: {:?}, mean: {:?}, p50: {:?}, p95: {:?}, p99: {:?}, spikes: {}",
+            self.count, self.min, self.max, self.mean, self.p50, self.p95, self.p99, self.spikes
+        )
+    }
+}
+
+/// Collect the statistics of the frame lengths over a rolling window.
+#[derive(Debug)]
+pub struct FrameStatistics {
+    window_size: usize,
+    frames: VecDeque<Duration>,
+    target: Option<Duration>,
+    spike_factor: f32,
+    histogram: Option<FrameHistogram>,
+    total_frames: usize,
+    total_spikes: usize,
+}
+
+impl FrameStatistics {
+    pub fn new(window_size: usize) -> FrameStatistics {
+        assert!(window_size > 0);
+        FrameStatistics {
+            window_size,
+            frames: VecDeque::with_capacity(window_size),
+            target: None,
+            spike_factor: DEFAULT_SPIKE_FACTOR,
+            histogram: None,
+            total_frames: 0,
+            total_spikes: 0,
+        }
+    }
+
+    /// Set the target frame length used for the spike detection.
+    pub fn with_target(self, target: Duration) -> FrameStatistics {
+        FrameStatistics {
+            target: Some(target),
+            ..self
+        }
+    }
+
+    /// Set the multiplier of the target frame length above which a frame is considered to be a spike.
+    pub fn with_spike_factor(self, spike_factor: f32) -> FrameStatistics {
+        assert!(spike_factor > 0.);
+        FrameStatistics { spike_factor, ..self }
+    }
+
+    /// Collect a histogram of the frames in the window.
+    pub fn with_histogram(self, bucket_width: Duration, bucket_count: usize) -> FrameStatistics {
+        FrameStatistics {
+            histogram: Some(FrameHistogram::new(bucket_width, bucket_count)),
+            ..self
+        }
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    pub fn target(&self) -> Option<Duration> {
+        self.target
+    }
+
+    /// Return the number of frames added since creation.
+    pub fn total_frames(&self) -> usize {
+        self.total_frames
+    }
+
+    /// Return the number of spikes since creation.
+    pub fn total_spikes(&self) -> usize {
+        self.total_spikes
+    }
+
+    pub fn histogram(&self) -> Option<&FrameHistogram> {
+        self.histogram.as_ref()
+    }
+
+    /// Return if the frame length is considered to be a spike.
+    pub fn is_spike(&self, frame: Duration) -> bool {
+        match self.target {
+            Some(target) => frame.as_nanos() as f64 > target.as_nanos() as f64 * f64::from(self.spike_factor),
+            None => false,
+        }
+    }
+
+    /// Add the length of a frame and return if it is a spike.
+    pub fn add(&mut self, frame: Duration) -> bool {
+        if self.frames.len() == self.window_size {
+            let old = self.frames.pop_front().unwrap();
+            if let Some(ref mut histogram) = self.histogram {
+                histogram.remove(old);
+            }
+        }
+        self.frames.push_back(frame);
+        if let Some(ref mut histogram) = self.histogram {
+            histogram.add(frame);
+        }
+
+        self.total_frames += 1;
+        let is_spike = self.is_spike(frame);
+        if is_spike {
+            self.total_spikes += 1;
+        }
+        is_spike
+    }
+
+    /// Clear the window, the histogram and the counters.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        if let Some(ref mut histogram) = self.histogram {
+            histogram.counts.iter_mut().for_each(|c| *c = 0);
+        }
+        self.total_frames = 0;
+        self.total_spikes = 0;
+    }
+
+    /// Calculate the statistics of the frames in the window.
+    pub fn summary(&self) -> FrameSummary {
+        if self.frames.is_empty() {
+            return FrameSummary::default();
+        }
+
+        let mut sorted: Vec<_> = self.frames.iter().cloned().collect();
+        sorted.sort();
+        let count = sorted.len();
+        let sum = sorted.iter().fold(0u128, |sum, d| sum + d.as_nanos());
+        // nearest-rank percentile
+        let percentile = |p: usize| sorted[((count * p + 99) / 100).max(1) - 1];
+
+        FrameSummary {
+            count,
+            min: sorted[0],
+            max: sorted[count - 1],
+            mean: Duration::from_nanos((sum / count as u128) as u64),
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            spikes: sorted.iter().filter(|&&d| self.is_spike(d)).count(),
+        }
+    }
+
+    /// Return a multi-line text report including the histogram.
+    pub fn report(&self) -> String {
+        let mut report = format!("{}", self.summary());
+        if let Some(ref histogram) = self.histogram {
+            let last = histogram.counts.len() - 1;
+            for (i, count) in histogram.counts.iter().enumerate() {
+                let from = histogram.bucket_width * i as u32;
+                if i == last {
+                    report += &format!("\n  {:?}.. : {}", from, count);
+                } else {
+                    report += &format!("\n  {:?}..{:?} : {}", from, from + histogram.bucket_width, count);
+                }
+            }
+        }
+        report
+    }
+
+    /// Return a compact json report including the histogram, durations are given in microseconds.
+    pub fn to_json(&self) -> String {
+        let summary = self.summary().to_json();
+        match self.histogram {
+            Some(ref histogram) => {
+                let counts: Vec<_> = histogram.counts.iter().map(|c| c.to_string()).collect();
+                format!(
+                    r#"{{"summary":{},"histogram":{{"bucket_width":{},"counts":[{}]}}}}"#,
+                    summary,
+                    histogram.bucket_width.as_micros(),
+                    counts.join(",")
+                )
+            }
+            None => format!(r#"{{"summary":{}}}"#, summary),
+        }
+    }
+}
@@ -1,22 +1,35 @@
+use crate::time::{Clock, RealClock};
 use std::time::{Duration, Instant};
 
 /// Measure the time of a single frame
 #[derive(Debug)]
-pub struct FrameTimer {
+pub struct FrameTimer<C: Clock = RealClock> {
+    clock: C,
     frame_start: Option<Instant>,
     prev_frame_length: Duration,
 }
 
-impl FrameTimer {
-    pub fn new() -> FrameTimer {
+impl FrameTimer<RealClock> {
+    pub fn new() -> FrameTimer<RealClock> {
+        FrameTimer::with_clock(RealClock)
+    }
+}
+
+impl<C: Clock> FrameTimer<C> {
+    pub fn with_clock(clock: C) -> FrameTimer<C> {
         FrameTimer {
+            clock,
             frame_start: None,
             prev_frame_length: Duration::default(),
         }
     }
 
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
     pub fn start(&mut self) {
-        if let Some(prev_frame_start) = self.frame_start.replace(Instant::now()) {
+        if let Some(prev_frame_start) = self.frame_start.replace(self.clock.now()) {
             self.prev_frame_length = self.frame_start.map(|v| v.duration_since(prev_frame_start)).unwrap();
         }
     }
@@ -31,7 +44,7 @@ impl FrameTimer {
     }
 
     pub fn elapsed_exact(&self) -> Duration {
-        self.frame_start.map(|v| v.elapsed()).unwrap_or(Duration::default())
+        self.frame_start.map(|v| self.clock.elapsed(v)).unwrap_or(Duration::default())
     }
 
     pub fn elapsed_exact_s(&self) -> f32 {
@@ -1,27 +1,39 @@
-use crate::time::{BlendScale, ScaledBlender};
+use crate::time::{BlendScale, Clock, RealClock, ScaledBlender};
 use std::time::{Duration, Instant};
 
 /// Measure time for intra-frame interpolations
 #[derive(Debug)]
-pub struct LogicTimer {
+pub struct LogicTimer<C: Clock = RealClock> {
+    clock: C,
     start: Instant,
     frame_length: Duration,
     prev_blend: f32,
     cur_blend: f32,
 }
 
-impl LogicTimer {
-    pub fn new() -> LogicTimer {
+impl LogicTimer<RealClock> {
+    pub fn new() -> LogicTimer<RealClock> {
+        LogicTimer::with_clock(RealClock)
+    }
+}
+
+impl<C: Clock> LogicTimer<C> {
+    pub fn with_clock(clock: C) -> LogicTimer<C> {
         LogicTimer {
-            start: Instant::now(),
+            start: clock.now(),
+            clock,
             frame_length: Duration::default(),
             prev_blend: 0.,
             cur_blend: 0.,
         }
     }
 
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
     pub fn start_logic(&mut self, frame_length: Duration) {
-        self.start = Instant::now();
+        self.start = self.clock.now();
         self.frame_length = frame_length;
         self.prev_blend = 0.;
         self.cur_blend = 0.;
@@ -30,7 +42,7 @@ impl LogicTimer {
     pub fn start_frame(&mut self) {
         self.prev_blend = self.cur_blend;
 
-        let duration = self.start.elapsed();
+        let duration = self.clock.elapsed(self.start);
         self.cur_blend = if duration >= self.frame_length {
             1.
         } else {
@@ -1,9 +1,15 @@
+mod clock;
+mod fixedtimestep;
 mod framelimiter;
+mod framestatistics;
 mod frametimer;
 mod logictimer;
 mod scaledblander;
 
+pub use self::clock::*;
+pub use self::fixedtimestep::*;
 pub use self::framelimiter::*;
+pub use self::framestatistics::*;
 pub use self::frametimer::*;
 pub use self::logictimer::*;
 pub use self::scaledblander::*;
@@ -1,7 +1,9 @@
 use crate::arena::PinnedArena;
+use crate::retention::{self, DropUnused, RetentionPolicy, Unused};
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use std::{fmt, ops};
+use std::{fmt, ops, slice};
 
 /// Reference counted indexing of the store items in O(1).
 pub struct Index<D>(*mut Entry<D>);
@@ -55,6 +57,8 @@ struct Entry<D> {
     ref_count: AtomicUsize,
     /// The stored data
     value: D,
+    /// The last frame (drain) the entry was referenced
+    last_used: usize,
 }
 
 // Store data that requires exclusive lock
@@ -66,6 +70,9 @@ struct SharedData<D> {
 struct ExclusiveData<D> {
     arena: PinnedArena<Entry<D>>,
     requests: Vec<*mut Entry<D>>,
+    retention: Box<dyn RetentionPolicy<D>>,
+    /// Number of drains performed, used to track the last use of the entries
+    frame: usize,
 }
 
 impl<D> ExclusiveData<D> {
@@ -74,6 +81,7 @@ impl<D> ExclusiveData<D> {
         let entry = self.arena.allocate(Entry {
             ref_count: AtomicUsize::new(0),
             value: data,
+            last_used: self.frame,
         });
         let entry = entry as *mut Entry<D>;
 
@@ -100,28 +108,39 @@ impl<D> Store<D> {
             exclusive: Mutex::new(ExclusiveData {
                 arena: PinnedArena::new(),
                 requests: Vec::new(),
+                retention: Box::new(DropUnused),
+                frame: 0,
             }),
         }
     }
 
     /// Creates a new store with memory allocated for at least capacity items
-    pub fn new_with_capacity(_page_size: usize, capacity: usize) -> Store<D> {
+    pub fn new_with_capacity(page_size: usize, capacity: usize) -> Store<D> {
         Store {
             shared: RwLock::new(SharedData {
                 resources: Vec::with_capacity(capacity),
             }),
             exclusive: Mutex::new(ExclusiveData {
-                arena: PinnedArena::new(), /*Arena::_with_capacity(page_size, capacity)*/
+                arena: PinnedArena::new_with_capacity(page_size, capacity),
                 requests: Vec::with_capacity(capacity),
+                retention: Box::new(DropUnused),
+                frame: 0,
             }),
         }
     }
 
+    /// Sets the policy to decide which unreferenced items are released by the drains.
+    /// By default all the unreferenced items are released immediately.
+    pub fn with_retention_policy<P: 'static + RetentionPolicy<D>>(mut self, policy: P) -> Store<D> {
+        self.exclusive.get_mut().unwrap().retention = Box::new(policy);
+        self
+    }
+
     /// Aquire read lock.
     pub fn try_read(&self) -> Option<ReadGuard<'_, D>> {
         let shared = self.shared.try_read().ok()?;
         Some(ReadGuard {
-            _shared: shared,
+            shared,
             exclusive: &self.exclusive,
         })
     }
@@ -173,7 +192,7 @@ impl<D> Drop for Store<D> {
 
 /// Guarded read access to a store
 pub struct ReadGuard<'a, D> {
-    _shared: RwLockReadGuard<'a, SharedData<D>>,
+    shared: RwLockReadGuard<'a, SharedData<D>>,
     exclusive: &'a Mutex<ExclusiveData<D>>,
 }
 
@@ -198,6 +217,27 @@ impl<'a, D: 'a> ReadGuard<'a, D> {
         let entry = unsafe { &(*index.0) };
         &entry.value
     }
+
+    /// Returns the number of the active Index referencing the item (including the given one).
+    pub fn ref_count(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.ref_count.load(Ordering::Relaxed)
+    }
+
+    /// Iterate over the active items. The pending (not yet finalized) requests are not visited
+    /// as they are not accessible without locking.
+    pub fn iter(&self) -> Iter<'_, D> {
+        Iter {
+            active: self.shared.resources.iter(),
+            pending: [].iter(),
+        }
+    }
+
+    /// Returns the number of active items. The pending (not yet finalized) requests are not counted.
+    pub fn summary(&self) -> Summary {
+        Summary::collect(&self.shared.resources, &[])
+    }
 }
 
 impl<'a, 'i, D: 'a> ops::Index<&'i Index<D>> for ReadGuard<'a, D> {
@@ -230,30 +270,73 @@ impl<'a, D: 'a> WriteGuard<'a, D> {
         self.shared.resources.append(&mut self.locked_exclusive.requests);
     }
 
-    fn drain_impl<F: FnMut(&mut D) -> bool>(arena: &mut PinnedArena<Entry<D>>, v: &mut Vec<*mut Entry<D>>, filter: &mut F) {
+    fn collect_unused<F: FnMut(&mut D) -> bool>(
+        retention: &dyn RetentionPolicy<D>,
+        frame: usize,
+        v: &[*mut Entry<D>],
+        filter: &mut F,
+        candidates: &mut Vec<(Unused, *mut Entry<D>)>,
+    ) {
+        for &e in v.iter() {
+            let entry = unsafe { &mut *e };
+            if entry.ref_count.load(Ordering::Relaxed) != 0 {
+                entry.last_used = frame;
+            } else if filter(&mut entry.value) {
+                let unused = Unused {
+                    last_used: entry.last_used,
+                    cost: retention.cost(&entry.value),
+                };
+                candidates.push((unused, e));
+            }
+        }
+    }
+
+    fn drain_impl(arena: &mut PinnedArena<Entry<D>>, v: &mut Vec<*mut Entry<D>>, evicted: &HashSet<*mut Entry<D>>) {
         v.drain_filter(|&mut e| {
-            let e = unsafe { &mut *e };
-            if e.ref_count.load(Ordering::Relaxed) == 0 {
-                let drain = filter(&mut e.value);
-                if drain {
-                    arena.deallocate(e);
-                }
-                drain
-            } else {
-                false
+            let drain = evicted.contains(&e);
+            if drain {
+                arena.deallocate(unsafe { &mut *e });
             }
+            drain
         });
     }
 
     /// Drain unreferenced elements those specified by the predicate.
-    /// In other words, remove all unreferenced resources such that f(&mut data) returns true.
+    /// In other words, remove all unreferenced resources such that f(&mut data) returns true and
+    /// the retention policy of the store allows the eviction.
+    /// Each drain advances the frame counter used to track the last use of the items.
     pub fn drain_unused_filtered<F: FnMut(&mut D) -> bool>(&mut self, mut filter: F) {
         let exclusive = &mut *self.locked_exclusive;
-        Self::drain_impl(&mut exclusive.arena, &mut self.shared.resources, &mut filter);
-        Self::drain_impl(&mut exclusive.arena, &mut exclusive.requests, &mut filter);
+        exclusive.frame += 1;
+        let frame = exclusive.frame;
+
+        let mut candidates = Vec::new();
+        Self::collect_unused(
+            &*exclusive.retention,
+            frame,
+            &self.shared.resources,
+            &mut filter,
+            &mut candidates,
+        );
+        Self::collect_unused(
+            &*exclusive.retention,
+            frame,
+            &exclusive.requests,
+            &mut filter,
+            &mut candidates,
+        );
+
+        let evicted: HashSet<_> = retention::select_evicted(&mut *exclusive.retention, frame, candidates)
+            .into_iter()
+            .collect();
+        if !evicted.is_empty() {
+            Self::drain_impl(&mut exclusive.arena, &mut self.shared.resources, &evicted);
+            Self::drain_impl(&mut exclusive.arena, &mut exclusive.requests, &evicted);
+        }
     }
 
-    /// Drain all unreferenced items. Only the referenced items are kept in the store.
+    /// Drain all unreferenced items permitted by the retention policy.
+    /// With the default policy only the referenced items are kept in the store.
     pub fn drain_unused(&mut self) {
         self.drain_unused_filtered(|_| true)
     }
@@ -269,6 +352,34 @@ impl<'a, D: 'a> WriteGuard<'a, D> {
         let entry = unsafe { &mut (*index.0) };
         &mut entry.value
     }
+
+    /// Returns the number of the active Index referencing the item (including the given one).
+    pub fn ref_count(&self, index: &Index<D>) -> usize {
+        assert!(!index.0.is_null(), "Indexing is invalid");
+        let entry = unsafe { &(*index.0) };
+        entry.ref_count.load(Ordering::Relaxed)
+    }
+
+    /// Iterate over all the items including the pending requests.
+    pub fn iter(&self) -> Iter<'_, D> {
+        Iter {
+            active: self.shared.resources.iter(),
+            pending: self.locked_exclusive.requests.iter(),
+        }
+    }
+
+    /// Iterate over all the items including the pending requests with mutable access to the data.
+    pub fn iter_mut(&mut self) -> IterMut<'_, D> {
+        IterMut {
+            active: self.shared.resources.iter(),
+            pending: self.locked_exclusive.requests.iter(),
+        }
+    }
+
+    /// Returns the number of active and pending items.
+    pub fn summary(&self) -> Summary {
+        Summary::collect(&self.shared.resources, &self.locked_exclusive.requests)
+    }
 }
 
 impl<'a, 'i, D: 'a> ops::Index<&'i Index<D>> for WriteGuard<'a, D> {
@@ -284,3 +395,61 @@ impl<'a, 'i, D: 'a> ops::IndexMut<&'i Index<D>> for WriteGuard<'a, D> {
         self.at_mut(index)
     }
 }
+
+/// Diagnostic information of a store
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    /// Number of the finalized items
+    pub active: usize,
+    /// Number of the items waiting for finalize_requests
+    pub pending: usize,
+    /// Number of the items without any reference
+    pub unreferenced: usize,
+}
+
+impl Summary {
+    fn collect<D>(active: &[*mut Entry<D>], pending: &[*mut Entry<D>]) -> Summary {
+        let unreferenced = active
+            .iter()
+            .chain(pending.iter())
+            .filter(|&&entry| unsafe { (*entry).ref_count.load(Ordering::Relaxed) } == 0)
+            .count();
+        Summary {
+            active: active.len(),
+            pending: pending.len(),
+            unreferenced,
+        }
+    }
+}
+
+/// Iterator over the (index, &data) pairs of a store
+pub struct Iter<'a, D> {
+    active: slice::Iter<'a, *mut Entry<D>>,
+    pending: slice::Iter<'a, *mut Entry<D>>,
+}
+
+impl<'a, D: 'a> Iterator for Iter<'a, D> {
+    type Item = (Index<D>, &'a D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &mut self.pending;
+        let &entry = self.active.next().or_else(|| pending.next())?;
+        Some((Index::new(entry), unsafe { &(*entry).value }))
+    }
+}
+
+/// Iterator over the (index, &mut data) pairs of a store
+pub struct IterMut<'a, D> {
+    active: slice::Iter<'a, *mut Entry<D>>,
+    pending: slice::Iter<'a, *mut Entry<D>>,
+}
+
+impl<'a, D: 'a> Iterator for IterMut<'a, D> {
+    type Item = (Index<D>, &'a mut D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &mut self.pending;
+        let &entry = self.active.next().or_else(|| pending.next())?;
+        Some((Index::new(entry), unsafe { &mut (*entry).value }))
+    }
+}
@@ -145,3 +145,73 @@ fn stress() {
         assert_eq!(drop_counter.get(), drop_count);
     }
 }
+
+#[test]
+fn handles() {
+    init_test(module_path!());
+
+    let drop_counter = Cell::new(0);
+    {
+        let mut arena = IndexedArena::new();
+
+        let h1 = arena.insert(Node(1, DropTracker(&drop_counter)));
+        let h2 = arena.insert(Node(2, DropTracker(&drop_counter)));
+        let h3 = arena.insert(Node(3, DropTracker(&drop_counter)));
+        assert_eq!(arena.len(), 3);
+        assert_eq!(arena.get(h1).map(|n| n.0), Some(1));
+        assert_eq!(arena[h2].0, 2);
+        assert_eq!(arena.handle_of(h3.index()), Some(h3));
+
+        debug!("stale handle");
+        let node2 = arena.remove(h2).unwrap();
+        assert_eq!(node2.0, 2);
+        mem::drop(node2);
+        assert_eq!(drop_counter.get(), 1);
+        assert!(arena.remove(h2).is_none());
+
+        let h4 = arena.insert(Node(4, DropTracker(&drop_counter)));
+        assert_eq!(h4.index(), h2.index());
+        assert_ne!(h4, h2);
+        assert!(arena.get(h2).is_none());
+        assert!(arena.get_mut(h2).is_none());
+        assert!(!arena.contains(h2));
+        assert_eq!(arena.get(h4).map(|n| n.0), Some(4));
+
+        debug!("iterate");
+        let mut items: Vec<_> = arena.iter().map(|(h, n)| (h, n.0)).collect();
+        items.sort();
+        assert_eq!(items, vec![(h1, 1), (h4, 4), (h3, 3)]);
+        for (_, n) in arena.iter_mut() {
+            n.0 *= 10;
+        }
+        assert_eq!(arena[h1].0, 10);
+
+        debug!("retain");
+        arena.retain(|h, n| h != h1 && n.0 != 30);
+        assert_eq!(arena.len(), 1);
+        assert_eq!(drop_counter.get(), 3);
+        assert!(!arena.contains(h1));
+        assert!(!arena.contains(h3));
+        assert_eq!(arena[h4].0, 40);
+
+        debug!("drain");
+        let h5 = arena.insert(Node(5, DropTracker(&drop_counter)));
+        let drained: Vec<_> = arena.drain().map(|(h, n)| (h, n.0)).collect();
+        assert_eq!(drained.len(), 2);
+        assert!(drained.contains(&(h4, 40)));
+        assert!(drained.contains(&(h5, 5)));
+        assert!(arena.is_empty());
+        assert!(!arena.contains(h4));
+        assert!(!arena.contains(h5));
+        assert_eq!(drop_counter.get(), 5);
+
+        debug!("clear");
+        let h6 = arena.insert(Node(6, DropTracker(&drop_counter)));
+        arena.clear();
+        assert_eq!(drop_counter.get(), 6);
+        let h7 = arena.insert(Node(7, DropTracker(&drop_counter)));
+        assert!(!arena.contains(h6));
+        assert_eq!(arena[h7].0, 7);
+    }
+    assert_eq!(drop_counter.get(), 7);
+}
@@ -1,8 +1,12 @@
 use log::{debug, info, trace};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::{mem, thread};
+use std::time::Duration;
+use std::{fs, mem, thread};
 
-use shine_stdext::namedstore::{Data, Store};
+use shine_stdext::namedstore::{Data, LoadState, ModifiedTimeChecker, Store, Summary};
+use shine_stdext::retention::LruBudget;
 use shine_testutils::{init_test, init_test_no_thread};
 
 /// Resource id for test data
@@ -127,6 +131,370 @@ fn simple_single_threaded() {
     }
 }
 
+#[test]
+fn loader() {
+    init_test(module_path!());
+
+    let store = Store::<TestData>::new_with_loader(
+        |k: &TestDataId| {
+            if k.0 % 2 == 0 {
+                Ok(TestData::new(format!("loaded: {}", k.0)))
+            } else {
+                Err(format!("odd: {}", k.0))
+            }
+        },
+        2,
+    );
+
+    debug!("request");
+    let (r0, r1) = {
+        let mut store = store.try_read().unwrap();
+        let r0 = store.get_or_add_blocking(&TestDataId(0));
+        let r1 = store.get_or_add_blocking(&TestDataId(1));
+        assert_eq!(store.load_state(&r0), &LoadState::Pending);
+        assert_eq!(store.load_state(&r1), &LoadState::Pending);
+        assert!(store[&r0].0 == format!("id: {}", 0));
+        (r0, r1)
+    };
+
+    debug!("load");
+    for _ in 0..100 {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        if store.pending_load_count() == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    {
+        let store = store.try_read().unwrap();
+        assert_eq!(store.load_state(&r0), &LoadState::Ready);
+        assert!(store[&r0].0 == format!("loaded: {}", 0));
+        assert_eq!(store.load_state(&r1), &LoadState::Failed(format!("odd: {}", 1)));
+        assert!(store[&r1].0 == format!("id: {}", 1));
+    }
+
+    debug!("drop while loading");
+    {
+        let mut store = store.try_write().unwrap();
+        mem::drop(store.get_or_add(&TestDataId(2)));
+        store.finalize_requests();
+        store.drain_unused_filtered(|d| d.0 == format!("id: {}", 2));
+        assert!(store.get(&TestDataId(2)).is_none());
+    }
+
+    mem::drop(r0);
+    mem::drop(r1);
+    {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        store.drain_unused();
+        assert!(store.is_empty());
+    }
+}
+
+#[test]
+fn loader_panic() {
+    init_test(module_path!());
+
+    let store = Store::<TestData>::new_with_loader(
+        |k: &TestDataId| {
+            if k.0 % 2 == 0 {
+                Ok(TestData::new(format!("loaded: {}", k.0)))
+            } else {
+                panic!("odd: {}", k.0)
+            }
+        },
+        1,
+    );
+
+    debug!("request");
+    let refs: Vec<_> = {
+        let mut store = store.try_read().unwrap();
+        (0..4).map(|i| store.get_or_add_blocking(&TestDataId(i))).collect()
+    };
+
+    debug!("load");
+    for _ in 0..100 {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        if store.pending_load_count() == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    {
+        let store = store.try_read().unwrap();
+        for (i, r) in refs.iter().enumerate() {
+            if i % 2 == 0 {
+                assert_eq!(store.load_state(r), &LoadState::Ready);
+            } else {
+                assert_eq!(
+                    store.load_state(r),
+                    &LoadState::Failed(format!("Loader panicked: odd: {}", i))
+                );
+                assert!(store[r].0 == format!("id: {}", i));
+            }
+        }
+    }
+}
+
+#[test]
+fn hot_reload() {
+    init_test(module_path!());
+
+    let load_count = Arc::new(AtomicUsize::new(0));
+    let store = {
+        let load_count = load_count.clone();
+        Store::<TestData>::new_with_loader(
+            move |k: &TestDataId| {
+                let cnt = load_count.fetch_add(1, Ordering::Relaxed);
+                Ok(TestData::new(format!("loaded: {}, {}", k.0, cnt)))
+            },
+            1,
+        )
+    };
+
+    let wait_loads = || {
+        for _ in 0..100 {
+            let mut store = store.try_write().unwrap();
+            store.finalize_requests();
+            if store.pending_load_count() == 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    };
+
+    let r0 = store.try_read().unwrap().get_or_add_blocking(&TestDataId(0));
+    let r1 = store.try_read().unwrap().get_or_add_blocking(&TestDataId(1));
+    wait_loads();
+    {
+        let store = store.try_read().unwrap();
+        assert_eq!(store.version(&r0), 1);
+        assert_eq!(store.version(&r1), 1);
+    }
+
+    debug!("invalidate 0");
+    {
+        let mut store = store.try_write().unwrap();
+        assert!(store.invalidate(&TestDataId(0)));
+        assert!(!store.invalidate(&TestDataId(2)));
+        assert_eq!(store.load_state(&r0), &LoadState::Pending);
+        // data is kept until reload is completed
+        assert!(store[&r0].0 == format!("loaded: {}, {}", 0, 0));
+    }
+    wait_loads();
+    {
+        let store = store.try_read().unwrap();
+        assert_eq!(store.load_state(&r0), &LoadState::Ready);
+        assert_eq!(store.version(&r0), 2);
+        assert_eq!(store.version(&r1), 1);
+        assert!(store[&r0].0 == format!("loaded: {}, {}", 0, 2));
+        assert!(store.get_blocking(&TestDataId(0)).unwrap() == r0);
+    }
+
+    debug!("invalidate all");
+    {
+        let mut store = store.try_write().unwrap();
+        assert_eq!(store.invalidate_where(|_, _| true), 2);
+    }
+    wait_loads();
+    {
+        let store = store.try_read().unwrap();
+        assert_eq!(store.version(&r0), 3);
+        assert_eq!(store.version(&r1), 2);
+    }
+    assert_eq!(load_count.load(Ordering::Relaxed), 5);
+
+    mem::drop(r0);
+    mem::drop(r1);
+    {
+        let mut store = store.try_write().unwrap();
+        store.drain_unused();
+        assert!(store.is_empty());
+    }
+}
+
+/// Test resource with file path key
+struct FileData(usize);
+
+impl Data for FileData {
+    type Key = PathBuf;
+
+    fn from_key(k: PathBuf) -> FileData {
+        FileData(fs::read(&k).map(|d| d.len()).unwrap_or(0))
+    }
+}
+
+#[test]
+fn modified_time_checker() {
+    init_test(module_path!());
+
+    let path = std::env::temp_dir().join(format!("shine_namedstore_{}.txt", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let store = Store::<FileData>::new();
+    let mut checker = ModifiedTimeChecker::new();
+
+    let r = store.try_read().unwrap().get_or_add_blocking(&path);
+    {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        assert_eq!(checker.check(&mut store), 0);
+        assert_eq!(store.version(&r), 0);
+        assert_eq!(store[&r].0, 0);
+    }
+
+    debug!("create");
+    fs::write(&path, "12345").unwrap();
+    {
+        let mut store = store.try_write().unwrap();
+        assert_eq!(checker.check(&mut store), 1);
+        assert_eq!(store.version(&r), 1);
+        assert_eq!(store[&r].0, 5);
+        assert_eq!(checker.check(&mut store), 0);
+        assert_eq!(store.version(&r), 1);
+    }
+
+    debug!("remove");
+    fs::remove_file(&path).unwrap();
+    {
+        let mut store = store.try_write().unwrap();
+        assert_eq!(checker.check(&mut store), 1);
+        assert_eq!(store.version(&r), 2);
+        assert_eq!(store[&r].0, 0);
+    }
+
+    mem::drop(r);
+    {
+        let mut store = store.try_write().unwrap();
+        store.drain_unused();
+        assert!(store.is_empty());
+    }
+}
+
+#[test]
+fn lru_retention() {
+    init_test(module_path!());
+
+    let store = Store::<TestData>::new().with_retention_policy(LruBudget::new(2).with_max_age(3));
+    let has = |id| store.try_read().unwrap().get_blocking(&TestDataId(id)).is_some();
+    let drain = || {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        store.drain_unused();
+    };
+
+    let refs: Vec<_> = (0..4)
+        .map(|id| store.try_read().unwrap().get_or_add_blocking(&TestDataId(id)))
+        .collect();
+    drain(); // frame 1
+    let mut refs = refs.into_iter();
+
+    debug!("keep unused within budget");
+    mem::drop(refs.next());
+    drain(); // frame 2
+    assert!(has(0));
+    mem::drop(refs.next());
+    drain(); // frame 3
+    assert!(has(0) && has(1));
+
+    debug!("evict least recently used");
+    mem::drop(refs.next());
+    drain(); // frame 4
+    assert!(!has(0) && has(1) && has(2));
+
+    debug!("evict by age");
+    let r1 = store.try_read().unwrap().get_blocking(&TestDataId(1)).unwrap();
+    drain(); // frame 5
+    mem::drop(r1);
+    drain(); // frame 6
+    assert!(has(1) && has(2));
+    drain(); // frame 7
+    assert!(has(1) && !has(2));
+    drain(); // frame 8
+    drain(); // frame 9
+    assert!(!has(1));
+
+    mem::drop(refs);
+    {
+        let mut store = store.try_write().unwrap();
+        store.drain_unused_filtered(|_| true);
+        assert!(!store.is_empty());
+    }
+}
+
+#[test]
+fn iterate() {
+    init_test(module_path!());
+
+    let store = Store::<TestData>::new();
+
+    let r0 = store.try_read().unwrap().get_or_add_blocking(&TestDataId(0));
+    let r1 = store.try_read().unwrap().get_or_add_blocking(&TestDataId(1));
+    {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        mem::drop(store.get_or_add(&TestDataId(2)));
+    }
+
+    debug!("read");
+    {
+        let store = store.try_read().unwrap();
+        // pending items are not visible
+        let mut items: Vec<_> = store.iter().map(|(k, _, d)| (k.0, d.0.clone())).collect();
+        items.sort();
+        assert_eq!(items, vec![(0, format!("id: {}", 0)), (1, format!("id: {}", 1))]);
+        assert_eq!(
+            store.summary(),
+            Summary {
+                active: 2,
+                pending: 0,
+                unreferenced: 0,
+                loading: 0
+            }
+        );
+
+        let r00 = store.iter().find(|(k, _, _)| k.0 == 0).map(|(_, i, _)| i).unwrap();
+        assert!(r00 == r0);
+        assert_eq!(store.ref_count(&r0), 2);
+        mem::drop(r00);
+        assert_eq!(store.ref_count(&r0), 1);
+    }
+
+    debug!("write");
+    {
+        let mut store = store.try_write().unwrap();
+        assert_eq!(store.iter().count(), 3);
+        assert_eq!(
+            store.summary(),
+            Summary {
+                active: 2,
+                pending: 1,
+                unreferenced: 1,
+                loading: 0
+            }
+        );
+
+        for (k, _, d) in store.iter_mut() {
+            d.0 = format!("updated: {}", k.0);
+        }
+        assert!(store[&r0].0 == format!("updated: {}", 0));
+        assert!(store[&r1].0 == format!("updated: {}", 1));
+    }
+
+    mem::drop(r0);
+    mem::drop(r1);
+    {
+        let mut store = store.try_write().unwrap();
+        store.drain_unused();
+        assert!(store.is_empty());
+    }
+}
+
 #[test]
 fn simple_multi_threaded() {
     init_test_no_thread(module_path!()).expect("Single threaded test environment required");
@@ -0,0 +1,159 @@
+use log::{debug, trace};
+use rand::seq::SliceRandom;
+use std::cell::Cell;
+
+use shine_stdext::arena::PinnedArena;
+use shine_testutils::init_test;
+
+struct DropTracker<'a>(&'a Cell<usize>);
+
+impl<'a> Drop for DropTracker<'a> {
+    fn drop(&mut self) {
+        trace!("drop");
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+struct Node<'a>(i32, DropTracker<'a>);
+
+#[test]
+fn simple() {
+    init_test(module_path!());
+
+    let drop_counter = Cell::new(0);
+    {
+        let mut arena = PinnedArena::new_with_page_size(2);
+
+        debug!("store");
+        assert_eq!(arena.len(), 0);
+        assert_eq!(arena.page_count(), 0);
+
+        let n1 = arena.allocate(Node(1, DropTracker(&drop_counter))) as *mut Node;
+        let n2 = arena.allocate(Node(2, DropTracker(&drop_counter))) as *mut Node;
+        let n3 = arena.allocate(Node(3, DropTracker(&drop_counter))) as *mut Node;
+        assert_eq!(arena.len(), 3);
+        assert_eq!(arena.page_count(), 2);
+        assert_eq!(arena.capacity(), 4);
+
+        unsafe {
+            assert_eq!((*n1).0, 1);
+            assert_eq!((*n2).0, 2);
+            assert_eq!((*n3).0, 3);
+        }
+        assert_eq!(drop_counter.get(), 0);
+
+        debug!("remove");
+        arena.deallocate(unsafe { &mut *n2 });
+        assert_eq!(arena.len(), 2);
+        assert_eq!(drop_counter.get(), 1);
+
+        debug!("reuse");
+        let n4 = arena.allocate(Node(4, DropTracker(&drop_counter))) as *mut Node;
+        assert_eq!(n4, n2);
+        assert_eq!(arena.page_count(), 2);
+        unsafe {
+            assert_eq!((*n1).0, 1);
+            assert_eq!((*n3).0, 3);
+            assert_eq!((*n4).0, 4);
+        }
+
+        debug!("shrink");
+        arena.deallocate(unsafe { &mut *n3 });
+        assert_eq!(drop_counter.get(), 2);
+        let stat = arena.statistics();
+        assert_eq!(stat.page_count, 2);
+        assert_eq!(stat.empty_page_count, 1);
+        assert_eq!(stat.live, 2);
+        assert_eq!(stat.fragmentation(), 0.);
+        assert_eq!(arena.shrink(), 1);
+        assert_eq!(arena.page_count(), 1);
+        assert_eq!(arena.len(), 2);
+        unsafe {
+            assert_eq!((*n1).0, 1);
+            assert_eq!((*n4).0, 4);
+        }
+
+        let n5 = arena.allocate(Node(5, DropTracker(&drop_counter))) as *mut Node;
+        assert_eq!(arena.page_count(), 2);
+        unsafe {
+            assert_eq!((*n5).0, 5);
+        }
+    }
+    // items not deallocated explicitly are dropped with the arena
+    assert_eq!(drop_counter.get(), 5);
+}
+
+#[test]
+fn stress() {
+    init_test(module_path!());
+
+    for &page_size in [1usize, 3, 16, 256].iter() {
+        for &cnt in [1usize, 2, 5, 7, 100, 4000].iter() {
+            trace!("page_size: {}, count: {}", page_size, cnt);
+
+            let drop_counter = Cell::new(0);
+            {
+                let mut arena = PinnedArena::new_with_capacity(page_size, cnt);
+                let capacity = arena.capacity();
+                assert!(capacity >= cnt);
+
+                let mut items = Vec::new();
+                for i in 0..cnt {
+                    let item = arena.allocate(Node(i as i32, DropTracker(&drop_counter))) as *mut Node;
+                    items.push((i as i32, item));
+                }
+                assert_eq!(arena.len(), cnt);
+                assert_eq!(arena.capacity(), capacity);
+
+                items.shuffle(&mut rand::thread_rng());
+                for &(v, item) in items.iter() {
+                    assert_eq!(unsafe { (*item).0 }, v);
+                }
+
+                trace!("remove half");
+                let rem = cnt / 2;
+                for &(_, item) in items.iter().take(rem) {
+                    arena.deallocate(unsafe { &mut *item });
+                }
+                assert_eq!(arena.len(), cnt - rem);
+                assert_eq!(drop_counter.get(), rem);
+                for &(v, item) in items.iter().skip(rem) {
+                    assert_eq!(unsafe { (*item).0 }, v);
+                }
+
+                trace!("add back");
+                for item in items.iter_mut().take(rem) {
+                    item.0 = -item.0;
+                    item.1 = arena.allocate(Node(item.0, DropTracker(&drop_counter))) as *mut Node;
+                }
+                // freed slots are reused
+                assert_eq!(arena.capacity(), capacity);
+                for &(v, item) in items.iter() {
+                    assert_eq!(unsafe { (*item).0 }, v);
+                }
+
+                trace!("remove all");
+                for &(_, item) in items.iter() {
+                    arena.deallocate(unsafe { &mut *item });
+                }
+                assert!(arena.is_empty());
+                assert_eq!(drop_counter.get(), rem + cnt);
+                assert_eq!(arena.shrink(), capacity / page_size);
+                assert_eq!(arena.capacity(), 0);
+            }
+            assert_eq!(drop_counter.get(), cnt + cnt / 2);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Item is not owned by this arena")]
+fn deallocate_foreign() {
+    init_test(module_path!());
+
+    let mut arena = PinnedArena::new_with_page_size(2);
+    let mut other = PinnedArena::new_with_page_size(2);
+    arena.allocate(1);
+    let foreign = other.allocate(2);
+    arena.deallocate(foreign);
+}
@@ -0,0 +1,84 @@
+use shine_stdext::spscqueue::queue_channel;
+use shine_testutils::{init_test, init_test_no_thread};
+use std::rc::Rc;
+use std::thread;
+
+const ITER_COUNT: usize = 0x2ffff;
+
+#[test]
+fn single_threaded_logic() {
+    init_test(module_path!());
+
+    let (p, c) = queue_channel(3);
+    assert_eq!(p.capacity(), 4);
+    assert_eq!(c.capacity(), 4);
+    assert!(c.is_empty());
+    assert!(c.try_recv().is_none());
+
+    p.try_send(1).unwrap();
+    p.try_send(2).unwrap();
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.try_recv(), Some(1));
+    assert_eq!(c.try_recv(), Some(2));
+    assert!(c.try_recv().is_none());
+
+    for x in 3..7 {
+        p.try_send(x).unwrap();
+    }
+    assert!(p.is_full());
+    assert_eq!(p.try_send(7), Err(7));
+
+    assert_eq!(c.drain().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    assert!(c.is_empty());
+    assert!(!p.is_full());
+}
+
+#[test]
+fn drop_remaining() {
+    init_test(module_path!());
+
+    let item = Rc::new(());
+    {
+        let (p, c) = queue_channel(8);
+        for _ in 0..5 {
+            p.try_send(item.clone()).unwrap();
+        }
+        assert_eq!(Rc::strong_count(&item), 6);
+        c.try_recv();
+        assert_eq!(Rc::strong_count(&item), 5);
+        // partially consumed drain keeps the rest in the queue
+        c.drain().next();
+        assert_eq!(c.len(), 3);
+    }
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[test]
+fn multi_threaded_stress() {
+    init_test_no_thread(module_path!()).expect("Single threaded test environment required");
+
+    let (p, c) = queue_channel(64);
+
+    let tp = thread::spawn(move || {
+        let mut x = 0;
+        while x < ITER_COUNT {
+            if p.try_send(x).is_ok() {
+                x += 1;
+            }
+        }
+    });
+    let tc = thread::spawn(move || {
+        let mut expected = 0;
+        while expected < ITER_COUNT {
+            for x in c.drain() {
+                // no message is lost or reordered
+                assert_eq!(x, expected);
+                expected += 1;
+            }
+        }
+        assert!(c.try_recv().is_none());
+    });
+
+    tp.join().unwrap();
+    tc.join().unwrap();
+}
@@ -1,4 +1,4 @@
-use shine_stdext::spscstate::state_channel;
+use shine_stdext::spscstate::{multi_state_channel, state_channel};
 use shine_testutils::{init_test, init_test_no_thread};
 use std::thread;
 
@@ -205,3 +205,91 @@ fn multi_threaded_stress_big_buffer() {
     tp.join().unwrap();
     tc.join().unwrap();
 }
+
+#[test]
+fn multi_single_threaded_logic() {
+    init_test(module_path!());
+
+    let (mut p, mut c) = multi_state_channel(2);
+    let (c1, c2) = c.split_at_mut(1);
+    let (c1, c2) = (&mut c1[0], &mut c2[0]);
+
+    assert!(c1.receive().is_err());
+    assert!(c2.receive().is_err());
+
+    p.send(1).unwrap();
+    assert_eq!(c1.receive().unwrap(), 1);
+    assert!(c1.receive().is_err());
+
+    p.send(2).unwrap();
+    p.send(3).unwrap();
+    assert_eq!(c1.receive().unwrap(), 3);
+    assert!(c1.receive().is_err());
+    {
+        let b = c2.receive_buffer().unwrap();
+        assert_eq!(*b, 3);
+        assert_eq!(b.sequence(), 3);
+        assert_eq!(b.skipped(), 2);
+    }
+    assert!(c2.receive().is_err());
+
+    // producer is not blocked by the consumers
+    for x in 4..10 {
+        p.send(x).unwrap();
+    }
+    assert_eq!(p.sequence(), 9);
+    {
+        let b = c1.receive_buffer().unwrap();
+        assert_eq!(*b, 9);
+        assert_eq!(b.skipped(), 5);
+    }
+    assert_eq!(c1.sequence(), 9);
+}
+
+#[test]
+fn multi_threaded_stress_multi_consumer() {
+    init_test_no_thread(module_path!()).expect("Single threaded test environment required");
+
+    let (mut p, c) = multi_state_channel::<BigData>(3);
+
+    let tp = thread::spawn(move || {
+        for x in 0..ITER_COUNT {
+            let mut d = p.send_buffer().unwrap();
+            d.pre = 1;
+            d.x = x;
+            for i in 0..d.data.len() {
+                d.data[i] = x;
+            }
+            d.post = 1;
+        }
+    });
+
+    let tc: Vec<_> = c
+        .into_iter()
+        .map(|mut c| {
+            thread::spawn(move || {
+                let mut prev = -1;
+                loop {
+                    if let Ok(d) = c.receive_buffer() {
+                        assert_eq!(d.pre, 1);
+                        assert_eq!(d.post, 1);
+                        for i in 0..d.data.len() {
+                            assert_eq!(d.data[i], d.x);
+                        }
+                        assert!(prev < d.x);
+                        assert_eq!(d.sequence(), d.x as usize + 1);
+                        prev = d.x;
+                        if prev == ITER_COUNT - 1 {
+                            break;
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    tp.join().unwrap();
+    for t in tc {
+        t.join().unwrap();
+    }
+}
@@ -1,4 +1,5 @@
-use shine_stdext::stdext::SliceOrdExt;
+use rand::Rng;
+use shine_stdext::stdext::{SliceOrdExt, VecOrdExt};
 use shine_testutils::init_test;
 
 #[test]
@@ -26,3 +27,88 @@ fn lower_bound() {
     assert_eq!(b.lower_bound(&5), 6);
     assert_eq!(b.lower_bound(&6), 7);
 }
+
+#[test]
+fn upper_bound_equal_range() {
+    init_test(module_path!());
+
+    let b: [u32; 0] = [];
+    assert_eq!(b.upper_bound(&0), 0);
+    assert_eq!(b.equal_range(&0), 0..0);
+
+    let b = [1, 3, 3, 3, 5];
+    assert_eq!(b.upper_bound(&0), 0);
+    assert_eq!(b.upper_bound(&1), 1);
+    assert_eq!(b.upper_bound(&2), 1);
+    assert_eq!(b.upper_bound(&3), 4);
+    assert_eq!(b.upper_bound(&5), 5);
+    assert_eq!(b.upper_bound(&6), 5);
+    assert_eq!(b.equal_range(&3), 1..4);
+    assert_eq!(b.equal_range(&4), 4..4);
+    assert_eq!(b.equal_range(&5), 4..5);
+}
+
+#[test]
+fn gallop_lower_bound() {
+    init_test(module_path!());
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let len = rng.gen_range(0, 100);
+        let mut b: Vec<u32> = (0..len).map(|_| rng.gen_range(0, 50)).collect();
+        b.sort();
+        for x in 0..52 {
+            let expected = b.lower_bound(&x);
+            for hint in 0..len + 2 {
+                assert_eq!(b.gallop_lower_bound(hint, &x), expected, "{:?}, {}, {}", b, x, hint);
+            }
+        }
+    }
+}
+
+#[test]
+fn sorted_set_operations() {
+    init_test(module_path!());
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let mut a: Vec<u32> = (0..rng.gen_range(0, 100)).map(|_| rng.gen_range(0, 200)).collect();
+        let mut b: Vec<u32> = (0..rng.gen_range(0, 100)).map(|_| rng.gen_range(0, 200)).collect();
+        a.sort();
+        a.dedup();
+        b.sort();
+        b.dedup();
+
+        let union: Vec<_> = a.sorted_union(&b).cloned().collect();
+        let mut expected: Vec<_> = a.iter().chain(b.iter()).cloned().collect();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(union, expected);
+
+        let intersection: Vec<_> = a.sorted_intersection(&b).cloned().collect();
+        let expected: Vec<_> = a.iter().filter(|x| b.contains(x)).cloned().collect();
+        assert_eq!(intersection, expected);
+
+        let difference: Vec<_> = a.sorted_difference(&b).cloned().collect();
+        let expected: Vec<_> = a.iter().filter(|x| !b.contains(x)).cloned().collect();
+        assert_eq!(difference, expected);
+    }
+}
+
+#[test]
+fn sorted_insert() {
+    init_test(module_path!());
+
+    let mut v: Vec<(u32, u32)> = Vec::new();
+    assert_eq!(v.sorted_insert((3, 0)), 0);
+    assert_eq!(v.sorted_insert((1, 0)), 0);
+    assert_eq!(v.sorted_insert((5, 0)), 2);
+    assert_eq!(v.sorted_insert((3, 1)), 2);
+    assert_eq!(v, vec![(1, 0), (3, 0), (3, 1), (5, 0)]);
+
+    let mut v = vec![1, 3, 3, 5];
+    assert_eq!(v.sorted_insert(3), 3);
+    assert_eq!(v.sorted_insert(0), 0);
+    assert_eq!(v.sorted_insert(6), 6);
+    assert_eq!(v, vec![0, 1, 3, 3, 3, 5, 6]);
+}
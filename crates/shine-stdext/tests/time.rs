@@ -0,0 +1,242 @@
+use log::info;
+use shine_stdext::time::{
+    Clock, FixedTimestep, FrameLimit, FrameLimiter, FrameStatistics, FrameSummary, FrameTimer, LogicTimer, ManualClock,
+    ScaledClock,
+};
+use shine_testutils::init_test;
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn fixed_timestep() {
+    init_test(module_path!());
+
+    let mut timer = FixedTimestep::new(ms(10)).with_max_steps(4);
+    assert_eq!(timer.advance(ms(5)), 0);
+    assert!((timer.alpha() - 0.5).abs() < 1e-6);
+    assert_eq!(timer.advance(ms(5)), 1);
+    assert_eq!(timer.alpha(), 0.);
+    assert_eq!(timer.advance(ms(27)), 2);
+    assert!((timer.alpha() - 0.7).abs() < 1e-6);
+    assert_eq!(timer.step_count(), 3);
+    assert_eq!(timer.simulated_time(), ms(30));
+
+    // spiral of death: the steps are capped and the excess time is dropped
+    assert_eq!(timer.advance(ms(100)), 4);
+    assert_eq!(timer.accumulated(), ms(7));
+    assert_eq!(timer.dropped_time(), ms(60));
+    assert_eq!(timer.advance(ms(3)), 1);
+    assert_eq!(timer.accumulated(), ms(0));
+
+    let (w0, w1) = timer.get_blender().get_start_end_weight();
+    assert_eq!((w0, w1), (1., 0.));
+}
+
+#[test]
+fn fixed_timestep_scale_and_pause() {
+    init_test(module_path!());
+
+    let mut timer = FixedTimestep::new(ms(10));
+    timer.set_time_scale(2.);
+    assert_eq!(timer.advance(ms(10)), 2);
+    timer.set_time_scale(0.5);
+    assert_eq!(timer.advance(ms(10)), 0);
+    assert_eq!(timer.accumulated(), ms(5));
+
+    timer.pause();
+    assert!(timer.is_paused());
+    assert_eq!(timer.advance(ms(100)), 0);
+    assert_eq!(timer.accumulated(), ms(5));
+
+    timer.resume();
+    timer.set_time_scale(1.);
+    assert_eq!(timer.advance(ms(5)), 1);
+    assert_eq!(timer.step_count(), 3);
+
+    // first update only starts the measurement
+    timer.reset();
+    assert_eq!(timer.update(), 0);
+    assert_eq!(timer.accumulated(), ms(0));
+}
+
+#[test]
+fn manual_clock() {
+    init_test(module_path!());
+
+    let clock = ManualClock::new();
+    let start = clock.now();
+    clock.advance(ms(5));
+    assert_eq!(clock.elapsed(start), ms(5));
+    clock.sleep(ms(10));
+    assert_eq!(clock.elapsed(start), ms(15));
+
+    let scaled = ScaledClock::new(clock.clone(), 2.);
+    let scaled_start = scaled.now();
+    clock.advance(ms(10));
+    assert_eq!(scaled.elapsed(scaled_start), ms(20));
+    scaled.set_scale(0.5);
+    clock.advance(ms(10));
+    assert_eq!(scaled.elapsed(scaled_start), ms(25));
+    // sleeping 5ms on the scaled clock takes 10ms on the source
+    scaled.sleep(ms(5));
+    assert_eq!(clock.elapsed(start), ms(45));
+}
+
+#[test]
+fn timers_with_manual_clock() {
+    init_test(module_path!());
+
+    let clock = ManualClock::new();
+
+    let mut frame_timer = FrameTimer::with_clock(clock.clone());
+    frame_timer.start();
+    clock.advance(ms(16));
+    assert_eq!(frame_timer.elapsed_exact(), ms(16));
+    frame_timer.start();
+    assert_eq!(frame_timer.elapsed(), ms(16));
+
+    let mut logic_timer = LogicTimer::with_clock(clock.clone());
+    logic_timer.start_logic(ms(100));
+    clock.advance(ms(25));
+    logic_timer.start_frame();
+    let (w0, w1) = logic_timer.get_blender().get_start_end_weight();
+    assert!((w0 - 0.75).abs() < 1e-6 && (w1 - 0.25).abs() < 1e-6);
+
+    let mut timestep = FixedTimestep::with_clock(clock.clone(), ms(10));
+    assert_eq!(timestep.update(), 0);
+    clock.advance(ms(35));
+    assert_eq!(timestep.update(), 3);
+}
+
+#[test]
+fn frame_limiter_with_manual_clock() {
+    init_test(module_path!());
+
+    let clock = ManualClock::new();
+    clock.set_spin_step(Duration::from_micros(100));
+    let mut limiter = FrameLimiter::with_clock(clock.clone());
+
+    limiter.start();
+    clock.advance(ms(4));
+    assert_eq!(limiter.limit(FrameLimit::Sleep(ms(10))), -2000);
+    assert_eq!(limiter.work_time(), ms(4));
+    assert_eq!(limiter.sleep_time(), ms(4));
+
+    limiter.start();
+    clock.advance(ms(4));
+    assert_eq!(limiter.limit(FrameLimit::SleepSpin(ms(10))), 0);
+    assert_eq!(limiter.sleep_time(), ms(8));
+    assert_eq!(limiter.spin_time(), ms(2));
+
+    // frame is already too long, no waiting
+    limiter.start();
+    clock.advance(ms(12));
+    assert_eq!(limiter.limit(FrameLimit::Spin(ms(10))), 2000);
+    assert_eq!(limiter.spin_time(), ms(2));
+    assert_eq!(clock.total_elapsed(), ms(30));
+}
+
+#[test]
+fn frame_statistics() {
+    init_test(module_path!());
+
+    let mut stats = FrameStatistics::new(100).with_target(ms(10)).with_histogram(ms(5), 4);
+    assert_eq!(stats.summary(), FrameSummary::default());
+
+    for i in 1..=100 {
+        assert!(!stats.add(ms(i % 10 + 1)));
+    }
+    let summary = stats.summary();
+    assert_eq!(summary.count, 100);
+    assert_eq!(summary.min, ms(1));
+    assert_eq!(summary.max, ms(10));
+    assert_eq!(summary.mean, Duration::from_micros(5500));
+    assert_eq!(summary.p50, ms(5));
+    assert_eq!(summary.p95, ms(10));
+    assert_eq!(summary.spikes, 0);
+    assert_eq!(stats.histogram().unwrap().counts(), &[40, 50, 10, 0]);
+
+    // window is rolling, spikes are detected against the target
+    assert!(stats.add(ms(40)));
+    assert!(!stats.add(ms(15)));
+    let summary = stats.summary();
+    assert_eq!(summary.count, 100);
+    assert_eq!(summary.max, ms(40));
+    assert_eq!(summary.spikes, 1);
+    assert_eq!(stats.total_frames(), 102);
+    assert_eq!(stats.total_spikes(), 1);
+    assert_eq!(stats.histogram().unwrap().counts(), &[38, 50, 10, 2]);
+
+    info!("{}", stats.report());
+    let json = stats.to_json();
+    assert!(json.starts_with(r#"{"summary":{"count":100,"min":1000,"max":40000,"#));
+    assert!(json.ends_with(r#""histogram":{"bucket_width":5000,"counts":[38,50,10,2]}}"#));
+}
+
+#[test]
+fn adaptive_frame_limiter() {
+    init_test(module_path!());
+
+    let clock = ManualClock::new();
+    clock.set_spin_step(Duration::from_micros(10));
+    clock.set_sleep_overshoot(Duration::from_micros(400));
+    let mut limiter = FrameLimiter::with_clock(clock.clone()).with_adaptive_sleep_limit();
+    assert!(limiter.is_adaptive());
+    assert_eq!(limiter.sleep_limit(), ms(2));
+
+    // the limit converges to the overshoot with some margin
+    for _ in 0..100 {
+        limiter.start();
+        clock.advance(ms(5));
+        assert!(limiter.limit(FrameLimit::SleepSpin(ms(16))) >= 0);
+    }
+    assert_eq!(limiter.last_overshoot(), Duration::from_micros(400));
+    assert!(limiter.sleep_limit() >= Duration::from_micros(500));
+    assert!(limiter.sleep_limit() < Duration::from_micros(510));
+
+    // larger overshoot is learned immediately
+    clock.set_sleep_overshoot(ms(2));
+    limiter.start();
+    assert!(limiter.limit(FrameLimit::SleepSpin(ms(16))) > 0);
+    assert_eq!(limiter.sleep_limit(), Duration::from_micros(2500));
+    limiter.start();
+    assert_eq!(limiter.limit(FrameLimit::SleepSpin(ms(16))), 0);
+}
+
+#[test]
+fn period_frame_limiter() {
+    init_test(module_path!());
+
+    let clock = ManualClock::new();
+    clock.set_spin_step(Duration::from_micros(100));
+    let mut limiter = FrameLimiter::with_clock(clock.clone()).with_sleep_limit(ms(1));
+    let origin = clock.now();
+
+    // deadlines are computed from the previous deadline, the time between the frames is not lost
+    for i in 1..=10 {
+        limiter.start();
+        clock.advance(ms(3));
+        assert_eq!(limiter.limit(FrameLimit::Period(ms(10))), 0);
+        assert_eq!(clock.elapsed(origin), ms(10 * i));
+        clock.advance(Duration::from_micros(500));
+    }
+
+    // a late frame is compensated by the next one
+    limiter.start();
+    clock.advance(ms(13));
+    assert_eq!(limiter.limit(FrameLimit::Period(ms(10))), 3500);
+    limiter.start();
+    assert_eq!(limiter.limit(FrameLimit::Period(ms(10))), 0);
+    assert_eq!(clock.elapsed(origin), ms(120));
+
+    // missed deadlines are skipped
+    limiter.start();
+    clock.advance(ms(35));
+    assert_eq!(limiter.limit(FrameLimit::Period(ms(10))), 0);
+    limiter.start();
+    assert_eq!(limiter.limit(FrameLimit::Period(ms(10))), 0);
+    assert_eq!(clock.elapsed(origin), ms(165));
+}
@@ -1,6 +1,8 @@
 use log::{info, trace};
-use shine_stdext::unnamedstore::Store;
+use shine_stdext::retention::LruBudget;
+use shine_stdext::unnamedstore::{Store, Summary};
 use shine_testutils::{init_test, init_test_no_thread};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{mem, thread};
 
@@ -90,6 +92,115 @@ fn simple_single_threaded() {
     }
 }
 
+/// Test data tracking the dropped items
+struct TrackedData(String, Arc<AtomicUsize>);
+
+impl Drop for TrackedData {
+    fn drop(&mut self) {
+        self.1.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn cost_retention() {
+    init_test(module_path!());
+
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let store =
+        Store::<TrackedData>::new().with_retention_policy(LruBudget::new(10).with_cost_budget(8, |d: &TrackedData| d.0.len()));
+    let drain = || {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+        store.drain_unused();
+    };
+
+    let r0 = store.try_read().unwrap().add(TrackedData("zero".into(), dropped.clone()));
+    let r1 = store.try_read().unwrap().add(TrackedData("one".into(), dropped.clone()));
+    let r2 = store.try_read().unwrap().add(TrackedData("two".into(), dropped.clone()));
+    drain();
+
+    info!("keep within budget");
+    mem::drop(r0);
+    drain();
+    mem::drop(r1);
+    drain();
+    assert_eq!(dropped.load(Ordering::Relaxed), 0);
+
+    info!("evict least recently used over budget");
+    mem::drop(r2);
+    drain();
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    {
+        let mut store = store.try_write().unwrap();
+        assert!(!store.is_empty());
+    }
+
+    mem::drop(store);
+    assert_eq!(dropped.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn iterate() {
+    init_test(module_path!());
+
+    let store = Store::<TestData>::new();
+
+    let r0 = store.try_read().unwrap().add(TestData::new("zero"));
+    {
+        let mut store = store.try_write().unwrap();
+        store.finalize_requests();
+    }
+    let r1 = store.try_read().unwrap().add(TestData::new("one"));
+
+    info!("read");
+    {
+        let store = store.try_read().unwrap();
+        // pending items are not visible
+        let items: Vec<_> = store.iter().map(|(i, d)| (i, d.0.clone())).collect();
+        assert_eq!(items.len(), 1);
+        assert!(items[0].0 == r0 && items[0].1 == "zero");
+        assert_eq!(store.ref_count(&r0), 2);
+        mem::drop(items);
+        assert_eq!(store.ref_count(&r0), 1);
+        assert_eq!(
+            store.summary(),
+            Summary {
+                active: 1,
+                pending: 0,
+                unreferenced: 0
+            }
+        );
+    }
+
+    info!("write");
+    {
+        let mut store = store.try_write().unwrap();
+        for (_, d) in store.iter_mut() {
+            d.0 = format!("updated {}", d.0);
+        }
+        assert!(store[&r0].0 == "updated zero");
+        assert!(store[&r1].0 == "updated one");
+
+        mem::drop(r1);
+        assert_eq!(store.iter().count(), 2);
+        assert_eq!(
+            store.summary(),
+            Summary {
+                active: 1,
+                pending: 1,
+                unreferenced: 1
+            }
+        );
+    }
+
+    mem::drop(r0);
+    {
+        let mut store = store.try_write().unwrap();
+        store.drain_unused();
+        assert!(store.is_empty());
+    }
+}
+
 #[test]
 fn simple_multi_threaded() {
     init_test_no_thread(module_path!()).expect("Single threaded test environment required");
@@ -5,7 +5,7 @@ use interact_prompt;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rendy::factory::{Config as RendyConfig, Factory};
 use shine_ecs::world::{ResourceWorld, World};
-use shine_stdext::time::{FrameLimit, FrameLimiter};
+use shine_stdext::time::{FrameLimit, FrameLimiter, FrameStatistics};
 use std::env;
 use std::sync::{Arc, Weak};
 use std::time::Duration;
@@ -23,6 +23,10 @@ use self::app::{App, AppLogicHandler, AppRenderHandler};
 use self::input::*;
 use demo::Demo;
 
+/// Number of frames in the statistics window, the statistics are also logged with this period
+const STATISTICS_WINDOW: usize = 300;
+const RENDER_FRAME_LENGTH: Duration = Duration::from_millis(10);
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum EventResult {
     None,
@@ -40,12 +44,13 @@ struct AppRender {
 
 fn logic<A: App>(app: &A, app_logic: &RwLock<AppLogic>, app_render: &RwLock<AppRender>, stop_signal: Weak<()>) {
     let mut frame_limiter = FrameLimiter::new();
+    let mut frame_statistics = FrameStatistics::new(STATISTICS_WINDOW);
     let mut app = app.create_logic_handler();
 
     while stop_signal.upgrade().is_some() {
         let start = Instant::now();
         let world_frame_length = {
-            log::info!("logic update");
+            log::trace!("logic update");
             frame_limiter.start();
             let mut app_logic = app_logic.write();
             let logic_world = &mut app_logic.world;
@@ -58,10 +63,10 @@ fn logic<A: App>(app: &A, app_logic: &RwLock<AppLogic>, app_render: &RwLock<AppR
             let _ = frame_limiter.limit(FrameLimit::SleepSpin(world_frame_length));
             world_frame_length
         };
-        log::info!("logic update duration: {:?} {:?}", start.elapsed(), world_frame_length);
+        log::trace!("logic update duration: {:?} {:?}", start.elapsed(), world_frame_length);
 
         {
-            log::info!("sync render to logic");
+            log::trace!("sync render to logic");
             let mut app_logic = app_logic.write();
             let mut app_render = app_render.write();
 
@@ -76,7 +81,12 @@ fn logic<A: App>(app: &A, app_logic: &RwLock<AppLogic>, app_render: &RwLock<AppR
             RwLockWriteGuard::unlock_fair(app_render);
             RwLockWriteGuard::unlock_fair(app_logic);
         }
-        log::info!("logic duration: {:?} {:?}", start.elapsed(), world_frame_length);
+        log::trace!("logic duration: {:?} {:?}", start.elapsed(), world_frame_length);
+
+        frame_statistics.add(start.elapsed());
+        if frame_statistics.total_frames() % STATISTICS_WINDOW == 0 {
+            log::info!("logic frames: {}", frame_statistics.summary());
+        }
     }
 }
 
@@ -146,6 +156,7 @@ fn render<A: App>(app: &A, app_render: &RwLock<AppRender>, mut stop_signal: Opti
 
     let mut app = app.create_render_handler();
     let mut frame_limiter = FrameLimiter::new();
+    let mut frame_statistics = FrameStatistics::new(STATISTICS_WINDOW).with_target(RENDER_FRAME_LENGTH);
 
     loop {
         let start = Instant::now();
@@ -188,8 +199,13 @@ fn render<A: App>(app: &A, app_render: &RwLock<AppRender>, mut stop_signal: Opti
             RwLockWriteGuard::unlock_fair(app_render);
         }
 
-        let _ = frame_limiter.limit(FrameLimit::SleepSpin(Duration::from_millis(10)));
-        log::info!("render duration: {:?}", start.elapsed());
+        let _ = frame_limiter.limit(FrameLimit::SleepSpin(RENDER_FRAME_LENGTH));
+        if frame_statistics.add(start.elapsed()) {
+            log::debug!("render spike: {:?}", start.elapsed());
+        }
+        if frame_statistics.total_frames() % STATISTICS_WINDOW == 0 {
+            log::info!("render frames: {}", frame_statistics.summary());
+        }
     }
 }
 